@@ -1,309 +1,1041 @@
-use std::process::abort;
+use std::collections::BTreeSet;
 
-use chrono::Utc;
-use colour::{e_dark_yellow_ln, e_green_ln, e_red_ln};
+use log::{info, warn};
 
 use crate::action::git_flow_action_adapter::GitFlowActionAdapter;
+use crate::execution::git_backend::GitBackend;
+use crate::execution::hook_execution::{HookContext, HookExecution};
 use crate::flow::git_flow::GitFlow;
-use crate::GitExecution;
-use crate::support::enums::ProjectType;
-use crate::support::util::Util;
+use crate::support::config::GitFlowConfig;
+use crate::support::enums::{FlowType, HookPoint, ProjectType, ReleaseType, VerificationLevel, VersionScheme};
+use crate::support::failure::fail;
+use crate::support::report::FlowReporter;
+use crate::support::version::{RELEASE_SUFFIX, SNAPSHOT_SUFFIX, VersionUtil};
+use crate::success;
+use crate::tr;
 
 pub struct CommonGitFlowAction {
     pub git_flow_action_adapter: Box<dyn GitFlowActionAdapter>,
-
+    pub git_backend: Box<dyn GitBackend>,
+    pub config: GitFlowConfig,
 }
 
 impl CommonGitFlowAction {
 
-    pub fn new(git_flow_action_adapter: Box<dyn GitFlowActionAdapter>) -> Box<CommonGitFlowAction> {
+    pub fn new(git_flow_action_adapter: Box<dyn GitFlowActionAdapter>, git_backend: Box<dyn GitBackend>, config: GitFlowConfig) -> Box<CommonGitFlowAction> {
         Box::new(CommonGitFlowAction{
-            git_flow_action_adapter
+            git_flow_action_adapter,
+            git_backend,
+            config,
         })
     }
-}
 
-impl GitFlow for CommonGitFlowAction {
-    fn project_type(&self) -> ProjectType {
-        self.git_flow_action_adapter.current_project_type()
+    fn run_hook(&self, hook_point: HookPoint, hook_context: &HookContext) {
+        HookExecution::run(&hook_point, self.config.hooks.commands_of(&hook_point), hook_context);
     }
 
-    fn verify_project(&self) {
-        self.git_flow_action_adapter.verify_project()
+    /// Modifies the project version and the versions of the replacers, then runs the post-version-change hooks
+    fn modify_version(&self, new_version: &str, hook_context: &mut HookContext) {
+        // the version is only read for the hooks and the report, reading it may run the build tool
+        hook_context.old_version = match hook_context.new_version.take() {
+            Some(previous_version) => Some(previous_version),
+            None if self.config.hooks.is_empty() && !FlowReporter::is_recording() => None,
+            None => self.git_flow_action_adapter.get_current_project_version(),
+        };
+        self.git_flow_action_adapter.modify_new_version(new_version);
+        for version_replacer in &self.config.version_replacers {
+            if let Err(message) = version_replacer.replace(new_version) {
+                fail(&message);
+            }
+        }
+        FlowReporter::record_version_change(hook_context.old_version.as_deref(), new_version);
+        hook_context.new_version = Some(String::from(new_version));
+        self.run_hook(HookPoint::PostVersionChange, hook_context);
     }
 
-    fn do_feature(&self) {
-        GitExecution::fetch();
-        GitExecution::switch_branch(&String::from(Self::DEVELOP_BRANCH));
-        GitExecution::pull();
-        let new_version = &self.git_flow_action_adapter.get_current_project_version()
-            .unwrap_or(String::from("1.0.0-SNAPSHOT"));
-        let option_actual_new_version = Util::substring_after(new_version, "v");
-        if option_actual_new_version.is_none() {
-            e_red_ln!("Can not extract new version number for :{}",new_version);
-            abort();
-        }
-        let actual_new_version = &option_actual_new_version.unwrap();
-        let option_version_number = Util::substring_before(actual_new_version, "-SNAPSHOT");
-        if option_version_number.is_none() {
-            e_red_ln!("Can not extract new version number for :{}",actual_new_version);
-            abort();
+    /// Stages all changes and commits, the commit is recorded into the report
+    fn commit(&self, message: &str) {
+        self.git_backend.commit(message);
+        if FlowReporter::is_recording() {
+            let id = self.git_backend.get_current_branch_name()
+                .and_then(|branch_name| self.revision_of(&branch_name));
+            FlowReporter::record_commit(id, message);
+        }
+    }
+
+    /// Tags current HEAD, the tag is recorded into the report
+    fn tag(&self, tag_name: &str) {
+        self.git_backend.tag(tag_name);
+        FlowReporter::record_tag(tag_name);
+    }
+
+    /// Calendar versions have no major version to bump
+    fn check_major_supported(&self) {
+        if VersionScheme::CalVer.eq(&self.config.release.version_scheme) {
+            fail(&tr!(MajorNotSupported, VersionScheme::CalVer));
         }
-        let version_number = option_version_number.unwrap();
-        let new_branch_name = &(Self::FEATURE_BRANCH.to_owned() + "/" + version_number.as_str());
-        let remote_branch_exists = GitExecution::is_branch_exists(new_branch_name, true);
+    }
+
+    /// Switches to develop and reads its version
+    fn develop_version(&self) -> String {
+        self.git_backend.fetch();
+        self.git_backend.switch_branch(Self::DEVELOP_BRANCH);
+        self.git_backend.pull();
+        self.git_flow_action_adapter.get_current_project_version()
+            .unwrap_or(String::from("1.0.0-SNAPSHOT"))
+    }
+
+    /// Creates the feature branch of the version number from develop with its development version,
+    /// switches to the branch instead if it exists
+    fn start_feature(&self, develop_version: &str, version_number: &str, mut hook_context: HookContext) {
+        let new_branch_name = &(Self::FEATURE_BRANCH.to_owned() + "/" + version_number);
+        hook_context.branch = Some(new_branch_name.to_string());
+        let remote_branch_exists = self.git_backend.is_branch_exists(new_branch_name, true);
         if remote_branch_exists {
-            GitExecution::fetch();
-            GitExecution::switch_branch(new_branch_name);
-            GitExecution::pull();
-            e_green_ln!("Feature branch exists ,Branch name : {}",new_branch_name);
+            self.git_backend.fetch();
+            self.git_backend.switch_branch(new_branch_name);
+            self.git_backend.pull();
+            self.run_hook(HookPoint::PostFlow, &hook_context);
+            success!("{}", tr!(FeatureBranchExists, new_branch_name));
             return;
         }
-        let local_branch_exists = GitExecution::is_branch_exists(new_branch_name, false);
+        let local_branch_exists = self.git_backend.is_branch_exists(new_branch_name, false);
         if local_branch_exists {
-            GitExecution::switch_branch(new_branch_name);
-            e_green_ln!("Feature branch exists ,Branch name : {}",new_branch_name);
+            self.git_backend.switch_branch(new_branch_name);
+            self.run_hook(HookPoint::PostFlow, &hook_context);
+            success!("{}", tr!(FeatureBranchExists, new_branch_name));
             return;
         }
-        GitExecution::create_new_branch_from_current(new_branch_name);
-        GitExecution::checkout_branch(new_branch_name);
-        self.git_flow_action_adapter.modify_new_version(new_version);
-        let anything_changed = GitExecution::are_there_anything_changed();
+        self.git_backend.create_new_branch_from_current(new_branch_name);
+        self.git_backend.checkout_branch(new_branch_name);
+        hook_context.new_version = Some(develop_version.to_string());
+        let new_version = version_number.to_owned() + SNAPSHOT_SUFFIX;
+        self.modify_version(&new_version, &mut hook_context);
+        let anything_changed = self.git_backend.are_there_anything_changed();
         if anything_changed {
-            GitExecution::commit(String::from("new feature: ").to_owned() + version_number.to_string().as_str())
+            self.commit(&("new feature: ".to_owned() + version_number))
+        }
+        self.run_hook(HookPoint::PostFlow, &hook_context);
+        success!("{}", tr!(FeatureFlowCompleted, new_branch_name));
+    }
+
+    /// Switches to master and computes the version of the test branch to release, the next major or the next release
+    fn test_version_number(&self, major: bool) -> String {
+        self.git_backend.fetch();
+        self.git_backend.switch_branch(Self::MASTER_BRANCH);
+        self.git_backend.pull();
+        let last_tag_name = self.git_backend.get_last_tag_name(Self::MASTER_BRANCH);
+        if major {
+            VersionUtil::next_major_release_version_number(last_tag_name)
+        } else {
+            self.config.release.next_release_version_number_of_test_branches(last_tag_name, &self.test_version_numbers())
         }
-        e_green_ln!("Feature flow execution completed,Branch name : {}",new_branch_name);
+    }
+
+    /// The version numbers of the local and remote test branches
+    fn test_version_numbers(&self) -> Vec<String> {
+        let prefix = Self::TEST_BRANCH.to_owned() + "/";
+        self.git_backend.list_all_branch().into_iter()
+            .chain(self.git_backend.list_remote_branch())
+            .filter_map(|branch_name| branch_name.strip_prefix(prefix.as_str()).map(String::from))
+            .collect::<Vec<String>>()
+    }
+
+    /// The latest release candidate of the version tagged on the test branch,
+    /// warns about the commits of the branch after it which no candidate contains
+    fn promoted_candidate_of(&self, version_number: &str, test_branch_name: &str) -> Option<String> {
+        let identifier = &self.config.release.candidate_identifier;
+        let candidate_tag_name = VersionUtil::candidate_numbers(version_number, identifier, &self.git_backend.list_tags())
+            .into_iter()
+            .map(|candidate_number| VersionUtil::candidate_tag_name(&VersionUtil::candidate_version(version_number, identifier, candidate_number)))
+            .find(|candidate_tag_name| {
+                self.git_backend.get_ahead_behind(candidate_tag_name, test_branch_name)
+                    .map(|(ahead, _)| ahead == 0)
+                    .unwrap_or(false)
+            })?;
+        // the commit moving back to the development version always follows the candidate
+        let commit_count = self.git_backend.get_ahead_behind(test_branch_name, &candidate_tag_name)
+            .map(|(ahead, _)| ahead)
+            .unwrap_or_default();
+        if commit_count > 1 {
+            warn!("{}", tr!(CandidateOutdated, test_branch_name, commit_count - 1, candidate_tag_name));
+        }
+        info!("{}", tr!(CandidatePromoted, candidate_tag_name));
+        Some(candidate_tag_name)
+    }
+
+    /// Merges the test branch of the version number into master, releases it and moves master and develop to the next development version
+    fn release_test(&self, to_release_version_number: &str, mut hook_context: HookContext) {
+        let new_feature_version_number = self.next_development_version_number(to_release_version_number);
+        let to_release_branch_name = Self::TEST_BRANCH.to_owned() + "/" + to_release_version_number;
+        hook_context.branch = Some(to_release_branch_name.clone());
+        let remote_branch_exist = self.git_backend.is_branch_exists(&to_release_branch_name, true);
+        let local_branch_exist = self.git_backend.is_branch_exists(&to_release_branch_name, false);
+        if remote_branch_exist || local_branch_exist {
+            self.git_backend.fetch();
+            self.git_backend.switch_branch(&to_release_branch_name);
+            if remote_branch_exist {
+                self.git_backend.pull();
+            }
+        } else {
+            fail(&tr!(TestBranchNotExists, to_release_branch_name));
+        }
+        let promoted_candidate = self.promoted_candidate_of(to_release_version_number, &to_release_branch_name);
+        self.git_backend.checkout_branch(Self::MASTER_BRANCH);
+        self.git_backend.pull();
+        let master_revision = self.revision_of(Self::MASTER_BRANCH);
+        self.git_backend.merge_to_current(&to_release_branch_name);
+        let release_version = to_release_version_number.to_owned() + RELEASE_SUFFIX;
+        self.modify_version(&release_version, &mut hook_context);
+        self.run_pre_release_commit_hook(Self::MASTER_BRANCH, &master_revision, &hook_context);
+        self.verify_release(Self::MASTER_BRANCH, &master_revision);
+        let anything_changed = self.git_backend.are_there_anything_changed();
+        if anything_changed {
+            let mut message = "release: ".to_owned() + to_release_version_number;
+            if let Some(promoted_candidate) = &promoted_candidate {
+                message = message + "\n\npromoted from " + promoted_candidate.as_str();
+            }
+            self.commit(&message);
+        }
+        let actual_tag_name = VersionUtil::release_tag_name(to_release_version_number);
+        self.tag(&actual_tag_name);
+        hook_context.tag = Some(actual_tag_name.clone());
+        self.run_hook(HookPoint::PostTag, &hook_context);
+        let next_feature_version = new_feature_version_number.to_owned() + SNAPSHOT_SUFFIX;
+        self.modify_version(&next_feature_version, &mut hook_context);
+        let anything_changed = self.git_backend.are_there_anything_changed();
+        if anything_changed {
+            self.commit(&("back to : ".to_owned() + next_feature_version.as_str()));
+        }
+        self.git_backend.switch_branch(Self::DEVELOP_BRANCH);
+        self.git_backend.pull();
+        self.git_backend.merge_to_current(Self::MASTER_BRANCH);
+        let next_feature_branch_name = Self::FEATURE_BRANCH.to_owned() + "/" + new_feature_version_number.as_str();
+        self.git_backend.create_new_branch_from_current(&next_feature_branch_name);
+        self.git_backend.checkout_branch(&next_feature_branch_name);
+        self.run_hook(HookPoint::PostFlow, &hook_context);
+        success!("{}", tr!(ReleaseTestCompleted, actual_tag_name, next_feature_branch_name));
+    }
+
+    /// Verifies the branch to release before the release commit, the verification may leave untracked
+    /// output behind but must not change tracked files, as the release commit stages them.
+    /// The branch is reset to the revision before the release and the release is cancelled if it fails
+    fn verify_release(&self, branch_name: &str, revision: &Option<String>) {
+        let verification_level = &self.config.release.verification;
+        if VerificationLevel::Skip.eq(verification_level) {
+            warn!("{}", tr!(VerificationSkipped));
+            return;
+        }
+        let changed_files = self.git_backend.list_changed_files();
+        let verification_result = self.verify_project(verification_level).and_then(|_| {
+            let verified_changed_files = self.git_backend.list_changed_files();
+            let changed_by_verification = changed_files.keys()
+                .chain(verified_changed_files.keys())
+                .filter(|path| changed_files.get(*path) != verified_changed_files.get(*path))
+                .map(String::as_str)
+                .collect::<BTreeSet<&str>>();
+            if changed_by_verification.is_empty() {
+                Ok(())
+            } else {
+                Err(tr!(VerificationChangedFiles, changed_by_verification.into_iter().collect::<Vec<&str>>().join(" , ")))
+            }
+        });
+        if let Err(message) = verification_result {
+            self.reset_release(branch_name, revision);
+            fail(&tr!(VerificationFailed, branch_name, message));
+        }
+    }
+
+    /// Runs the pre-release-commit hooks, the branch is reset like a failed verification if one fails
+    fn run_pre_release_commit_hook(&self, branch_name: &str, revision: &Option<String>, hook_context: &HookContext) {
+        let hook_point = HookPoint::PreReleaseCommit;
+        if let Err(message) = HookExecution::try_run(&hook_point, self.config.hooks.commands_of(&hook_point), hook_context) {
+            self.reset_release(branch_name, revision);
+            fail(&tr!(ReleaseHookFailed, branch_name, message));
+        }
+    }
+
+    /// Points the branch back to the revision before the release, dropping the merge and the version change
+    fn reset_release(&self, branch_name: &str, revision: &Option<String>) {
+        if let Some(revision) = revision {
+            self.git_backend.force_branch(branch_name, revision);
+        }
+    }
+
+    /// The version number master and develop move to after the release, computed before anything is released
+    fn next_development_version_number(&self, to_release_version_number: &str) -> String {
+        match self.config.release.next_development_version_number(to_release_version_number) {
+            Some(version_number) => version_number,
+            None => fail(&tr!(NextDevelopmentVersionFailed, to_release_version_number)),
+        }
+    }
+
+    fn revision_of(&self, branch_name: &str) -> Option<String> {
+        self.git_backend.list_refs().remove(&("refs/heads/".to_owned() + branch_name))
+    }
+
+    /// Switches to the branch, pulls it if it is on remote
+    fn switch_and_pull(&self, branch_name: &str) {
+        self.git_backend.switch_branch(branch_name);
+        if self.git_backend.is_branch_exists(branch_name, true) {
+            self.git_backend.pull();
+        }
+    }
+
+    /// The hotfix version of the support branch's line, `support/1.3` to `1.3.5` when 1.3.4 is released,
+    /// `support/2026.10.0` to `2026.10.0.2` when 2026.10.0.1 is released with CalVer
+    fn hotfix_version_number_of_support(&self, support_branch_name: &str) -> String {
+        let option_version_number = support_branch_name
+            .strip_prefix(&(Self::SUPPORT_BRANCH.to_owned() + "/"))
+            .and_then(|line| self.config.release.next_hotfix_version_number_in_line(line, &self.git_backend.list_tags()));
+        match option_version_number {
+            Some(version_number) => version_number,
+            None => fail(&tr!(NotSupportBranch, support_branch_name)),
+        }
+    }
+
+    /// The hotfix version of the line of the release tag
+    fn hotfix_version_number_of_tag(&self, base_tag_name: &str) -> String {
+        let option_version_number = VersionUtil::version_number_of_tag(base_tag_name)
+            .and_then(|version_number| self.config.release.hotfix_line_of(&version_number))
+            .and_then(|line| self.config.release.next_hotfix_version_number_in_line(&line, &self.git_backend.list_tags()));
+        match option_version_number {
+            Some(version_number) => version_number,
+            None => fail(&tr!(InvalidReleaseTag, base_tag_name)),
+        }
+    }
+}
+
+impl GitFlow for CommonGitFlowAction {
+    fn project_type(&self) -> ProjectType {
+        self.git_flow_action_adapter.current_project_type()
+    }
+
+    fn verify_project(&self, verification_level: &VerificationLevel) -> Result<(), String> {
+        self.git_flow_action_adapter.verify_project(verification_level)
+    }
+
+    fn do_feature(&self) {
+        let hook_context = HookContext::new(FlowType::Feature, None);
+        self.run_hook(HookPoint::PreFlow, &hook_context);
+        let develop_version = self.develop_version();
+        let option_version_number = VersionUtil::version_number_of_snapshot(&develop_version);
+        if option_version_number.is_none() {
+            fail(&tr!(ExtractNewVersionFailed, develop_version));
+        }
+        self.start_feature(&develop_version, &option_version_number.unwrap(), hook_context);
+    }
+
+    fn do_major_feature(&self) {
+        self.check_major_supported();
+        let hook_context = HookContext::new(FlowType::Feature, None);
+        self.run_hook(HookPoint::PreFlow, &hook_context);
+        let develop_version = self.develop_version();
+        let option_version_number = VersionUtil::version_number_of_snapshot(&develop_version)
+            .and_then(|version_number| VersionUtil::next_major(&version_number));
+        if option_version_number.is_none() {
+            fail(&tr!(ExtractNewVersionFailed, develop_version));
+        }
+        self.start_feature(&develop_version, &option_version_number.unwrap(), hook_context);
     }
 
     fn do_hotfix(&self) {
-        GitExecution::fetch();
-        GitExecution::switch_branch(&String::from(Self::MASTER_BRANCH));
-        GitExecution::pull();
-        let option_last_tag_name = GitExecution::get_last_tag_name();
+        let mut hook_context = HookContext::new(FlowType::Hotfix, None);
+        self.run_hook(HookPoint::PreFlow, &hook_context);
+        self.git_backend.fetch();
+        self.git_backend.switch_branch(Self::MASTER_BRANCH);
+        self.git_backend.pull();
+        let option_last_tag_name = self.git_backend.get_last_tag_name(Self::MASTER_BRANCH);
         if option_last_tag_name.is_none() {
-            e_red_ln!("There is no tag exists in master branch,Hotfix branch can not be created");
-            abort();
-        }
-        let last_tag_name = option_last_tag_name.unwrap();
-        let substring = Util::substring_after(&last_tag_name, "v");
-        if substring.is_none() {
-            e_red_ln!("Can not extract version from tag");
-            abort();
-        }
-        let option_latest_version_number = Util::substring_before(&substring.unwrap(), ".RELEASE");
-        if option_latest_version_number.is_none() {
-            e_red_ln!("Can not extract latest version number from tag");
-            abort();
-        }
-        let mut version_numbers = option_latest_version_number.unwrap()
-            .split(".")
-            .map(|item| String::from(item))
-            .collect::<Vec<String>>();
-        version_numbers[2] = ((&version_numbers[2].parse::<i32>().unwrap()) + 1).to_string();
-        let new_version_number = version_numbers.join(".");
+            fail(&tr!(NoTagForHotfix));
+        }
+        let option_new_version_number = self.config.release.next_hotfix_version_number(option_last_tag_name);
+        if option_new_version_number.is_none() {
+            fail(&tr!(ExtractLatestVersionFailed));
+        }
+        let new_version_number = option_new_version_number.unwrap();
         let new_branch_name = Self::HOTFIX_BRANCH.to_owned() + "/" + new_version_number.as_str();
-        let remote_branch_exists = GitExecution::is_branch_exists(&new_branch_name, true);
+        hook_context.branch = Some(new_branch_name.clone());
+        let remote_branch_exists = self.git_backend.is_branch_exists(&new_branch_name, true);
         if remote_branch_exists {
-            GitExecution::fetch();
-            GitExecution::switch_branch(&new_branch_name);
-            GitExecution::pull();
-            e_green_ln!("Hotfix branch exists ,Branch name :{}",new_branch_name);
+            self.git_backend.fetch();
+            self.git_backend.switch_branch(&new_branch_name);
+            self.git_backend.pull();
+            self.run_hook(HookPoint::PostFlow, &hook_context);
+            success!("{}", tr!(HotfixBranchExists, new_branch_name));
             return;
         }
-        let local_branch_exists = GitExecution::is_branch_exists(&new_branch_name, false);
+        let local_branch_exists = self.git_backend.is_branch_exists(&new_branch_name, false);
         if local_branch_exists {
-            GitExecution::switch_branch(&new_branch_name);
-            e_green_ln!("Hotfix branch exists ,Branch name :{}",new_branch_name);
+            self.git_backend.switch_branch(&new_branch_name);
+            self.run_hook(HookPoint::PostFlow, &hook_context);
+            success!("{}", tr!(HotfixBranchExists, new_branch_name));
             return;
         }
-        GitExecution::create_new_branch_from_current(&new_branch_name);
-        GitExecution::checkout_branch(&new_branch_name);
-        let new_version = new_version_number.as_str().to_owned() + "-SNAPSHOT";
-        self.git_flow_action_adapter.modify_new_version(&new_version);
-        let anything_changed = GitExecution::are_there_anything_changed();
+        self.git_backend.create_new_branch_from_current(&new_branch_name);
+        self.git_backend.checkout_branch(&new_branch_name);
+        let new_version = new_version_number.to_owned() + SNAPSHOT_SUFFIX;
+        self.modify_version(&new_version, &mut hook_context);
+        let anything_changed = self.git_backend.are_there_anything_changed();
         if anything_changed {
-            GitExecution::commit("new hotfix: ".to_owned() + new_version_number.as_str())
+            self.commit(&("new hotfix: ".to_owned() + new_version_number.as_str()))
         }
-        e_green_ln!("Hotfix flow execution completed,Branch name : {}",new_branch_name);
+        self.run_hook(HookPoint::PostFlow, &hook_context);
+        success!("{}", tr!(HotfixFlowCompleted, new_branch_name));
     }
 
     fn do_release_test(&self) {
-        GitExecution::fetch();
-        GitExecution::switch_branch(&String::from(Self::MASTER_BRANCH));
-        GitExecution::pull();
-        let to_release_version_number = GitExecution::get_last_tag_name()
-            .and_then(|last_tag_name| {
-                Util::substring_after(&last_tag_name, "v")
-            })
-            .and_then(|sub| {
-                Util::substring_before(&sub, ".RELEASE")
-            })
-            .map(|last_version_number| {
-                let mut vec = last_version_number.split(".")
-                    .map(|item| String::from(item))
-                    .collect::<Vec<String>>();
-                vec[1] = ((&vec[1].parse::<i32>().unwrap()) + 1).to_string();
-                vec[2] = String::from("0");
-                vec.join(".")
-            })
-            .unwrap_or(String::from("1.0.0"));
-        let to_release_branch_name = Self::TEST_BRANCH.to_owned().to_owned() + "/" + to_release_version_number.as_str();
-        let remote_branch_exist = GitExecution::is_branch_exists(&to_release_branch_name, true);
-        let local_branch_exist = GitExecution::is_branch_exists(&to_release_branch_name, false);
-        if remote_branch_exist || local_branch_exist {
-            GitExecution::fetch();
-            GitExecution::switch_branch(&to_release_branch_name);
-            if remote_branch_exist {
-                GitExecution::pull();
-            }
-        } else {
-            e_red_ln!("Test branch doesn't exists ,Branch name : {}",to_release_branch_name);
-            abort();
-        }
-        GitExecution::checkout_branch(&String::from(Self::MASTER_BRANCH));
-        GitExecution::pull();
-        GitExecution::merge_to_current(&to_release_branch_name);
-        let release_version = to_release_version_number.to_owned() + ".RELEASE";
-        self.git_flow_action_adapter.modify_new_version(&release_version);
-        let anything_changed = GitExecution::are_there_anything_changed();
+        let hook_context = HookContext::new(FlowType::Release, Some(ReleaseType::Test));
+        self.run_hook(HookPoint::PreFlow, &hook_context);
+        let to_release_version_number = self.test_version_number(false);
+        self.release_test(&to_release_version_number, hook_context);
+    }
+
+    fn do_release_major(&self) {
+        self.check_major_supported();
+        let hook_context = HookContext::new(FlowType::Release, Some(ReleaseType::Test));
+        self.run_hook(HookPoint::PreFlow, &hook_context);
+        let to_release_version_number = self.test_version_number(true);
+        self.release_test(&to_release_version_number, hook_context);
+    }
+
+    fn do_release_candidate(&self, major: bool) {
+        if major {
+            self.check_major_supported();
+        }
+        let mut hook_context = HookContext::new(FlowType::Release, Some(ReleaseType::Candidate));
+        self.run_hook(HookPoint::PreFlow, &hook_context);
+        let to_release_version_number = self.test_version_number(major);
+        let test_branch_name = Self::TEST_BRANCH.to_owned() + "/" + to_release_version_number.as_str();
+        hook_context.branch = Some(test_branch_name.clone());
+        let remote_branch_exist = self.git_backend.is_branch_exists(&test_branch_name, true);
+        let local_branch_exist = self.git_backend.is_branch_exists(&test_branch_name, false);
+        if !remote_branch_exist && !local_branch_exist {
+            fail(&tr!(TestBranchNotExists, test_branch_name));
+        }
+        self.switch_and_pull(&test_branch_name);
+        let current_version = match self.git_flow_action_adapter.get_current_project_version() {
+            Some(current_version) => current_version,
+            None => fail(&tr!(ReadBranchVersionFailed, test_branch_name)),
+        };
+        let identifier = &self.config.release.candidate_identifier;
+        let candidate_number = VersionUtil::candidate_numbers(&to_release_version_number, identifier, &self.git_backend.list_tags())
+            .first()
+            .map(|last_candidate_number| last_candidate_number + 1)
+            .unwrap_or(1);
+        let candidate_version = VersionUtil::candidate_version(&to_release_version_number, identifier, candidate_number);
+        let branch_revision = self.revision_of(&test_branch_name);
+        hook_context.new_version = Some(current_version.clone());
+        self.modify_version(&candidate_version, &mut hook_context);
+        self.run_pre_release_commit_hook(&test_branch_name, &branch_revision, &hook_context);
+        self.verify_release(&test_branch_name, &branch_revision);
+        let anything_changed = self.git_backend.are_there_anything_changed();
         if anything_changed {
-            GitExecution::commit("release: ".to_owned() + to_release_version_number.as_str());
-        }
-        let date_str = Utc::now().format("%Y%m%d").to_string();
-        let mut new_version_vec = Vec::new();
-        new_version_vec.push(release_version.as_str());
-        new_version_vec.push(&date_str.as_str());
-        let new_tag_name = new_version_vec.join(".");
-        let actual_tag_name = String::from("v".to_owned() + new_tag_name.as_str());
-        GitExecution::tag(&actual_tag_name);
-        let mut next_version_number_vec = to_release_version_number.split(".")
-            .map(|item| String::from(item))
-            .collect::<Vec<String>>();
-        next_version_number_vec[1] = ((&next_version_number_vec[1].parse::<i32>().unwrap()) + 1).to_string();
-        let new_feature_version_number = next_version_number_vec.join(".");
-        let next_feature_version = new_feature_version_number.to_owned() + "-SNAPSHOT";
-        self.git_flow_action_adapter.modify_new_version(&next_feature_version);
-        let anything_changed = GitExecution::are_there_anything_changed();
+            self.commit(&("release candidate: ".to_owned() + candidate_version.as_str()));
+        }
+        let candidate_tag_name = VersionUtil::candidate_tag_name(&candidate_version);
+        self.tag(&candidate_tag_name);
+        hook_context.tag = Some(candidate_tag_name.clone());
+        self.run_hook(HookPoint::PostTag, &hook_context);
+        self.modify_version(&current_version, &mut hook_context);
+        let anything_changed = self.git_backend.are_there_anything_changed();
         if anything_changed {
-            GitExecution::commit("back to : ".to_owned() + next_feature_version.as_str());
+            self.commit(&("back to : ".to_owned() + current_version.as_str()));
         }
-        GitExecution::switch_branch(&String::from(Self::DEVELOP_BRANCH));
-        GitExecution::pull();
-        GitExecution::merge_to_current(&String::from(Self::MASTER_BRANCH));
-        let next_feature_branch_name = Self::FEATURE_BRANCH.to_owned() + "/" + new_feature_version_number.as_str();
-        GitExecution::create_new_branch_from_current(&next_feature_branch_name);
-        GitExecution::checkout_branch(&next_feature_branch_name);
-        e_green_ln!("Feature release flow execution completed,Release tag name : {} , Next feature branch : {}" ,actual_tag_name,next_feature_branch_name);
+        self.run_hook(HookPoint::PostFlow, &hook_context);
+        success!("{}", tr!(CandidateReleaseCompleted, candidate_tag_name, test_branch_name));
     }
 
     fn do_release_specific(&self, release_source_branch: String) {
-        GitExecution::fetch();
-        GitExecution::switch_branch(&release_source_branch);
-        GitExecution::pull();
+        let mut hook_context = HookContext::new(FlowType::Release, Some(ReleaseType::Specific));
+        hook_context.branch = Some(release_source_branch.clone());
+        self.run_hook(HookPoint::PreFlow, &hook_context);
+        self.git_backend.fetch();
+        self.git_backend.switch_branch(&release_source_branch);
+        self.git_backend.pull();
         let option_current_project_version = self.git_flow_action_adapter.get_current_project_version();
         if option_current_project_version.is_none() {
-            e_red_ln!("Can not get current project's version from branch : {}",release_source_branch);
-            abort();
+            fail(&tr!(ReadBranchVersionFailed, release_source_branch));
         }
         let current_project_version = option_current_project_version.unwrap();
-        let is_snapshot = current_project_version.ends_with("-SNAPSHOT");
+        let is_snapshot = current_project_version.ends_with(SNAPSHOT_SUFFIX);
         if !is_snapshot {
-            e_red_ln!("Current project's version is not a SNAPSHOT version,Version : {}" ,current_project_version);
-            abort();
+            fail(&tr!(NotSnapshotVersion, current_project_version));
         }
-        let option_to_release_version_number = Util::substring_before(&current_project_version, "-SNAPSHOT");
+        let option_to_release_version_number = VersionUtil::version_number_of_snapshot(&current_project_version);
         if option_to_release_version_number.is_none() {
-            e_red_ln!("Can not extract to release version number : {}" ,current_project_version);
-            abort();
+            fail(&tr!(ExtractReleaseVersionFailed, current_project_version));
         }
         let to_release_version_number = option_to_release_version_number.unwrap();
-        GitExecution::switch_branch(&String::from(Self::MASTER_BRANCH));
-        GitExecution::pull();
-        GitExecution::merge_to_current(&release_source_branch);
-        let release_version = to_release_version_number.as_str().to_owned() + ".RELEASE";
-        self.git_flow_action_adapter.modify_new_version(&release_version);
-        let anything_changed = GitExecution::are_there_anything_changed();
+        hook_context.new_version = Some(current_project_version.clone());
+        self.git_backend.switch_branch(Self::MASTER_BRANCH);
+        self.git_backend.pull();
+        let master_revision = self.revision_of(Self::MASTER_BRANCH);
+        self.git_backend.merge_to_current(&release_source_branch);
+        let release_version = to_release_version_number.to_owned() + RELEASE_SUFFIX;
+        self.modify_version(&release_version, &mut hook_context);
+        self.run_pre_release_commit_hook(Self::MASTER_BRANCH, &master_revision, &hook_context);
+        self.verify_release(Self::MASTER_BRANCH, &master_revision);
+        let anything_changed = self.git_backend.are_there_anything_changed();
         if anything_changed {
-            GitExecution::commit("release: ".to_owned() + to_release_version_number.as_str());
+            self.commit(&("release: ".to_owned() + to_release_version_number.as_str()));
         }
-        let date_str = Utc::now().format("%Y%m%d").to_string();
-        let mut new_version_vec = Vec::new();
-        new_version_vec.push(release_version.as_str());
-        new_version_vec.push(&date_str.as_str());
-        let new_tag_name = new_version_vec.join(".");
-        let actual_tag_name = String::from("v".to_owned() + new_tag_name.as_str());
-        GitExecution::tag(&actual_tag_name);
-        e_dark_yellow_ln!("Release flow of specific branch execution completed,you should merge master into other feature/test branch to keep code as same as mater's updates");
-        e_green_ln!("Specific release flow execution completed,Release tag name : {}",actual_tag_name);
+        let actual_tag_name = VersionUtil::release_tag_name(&to_release_version_number);
+        self.tag(&actual_tag_name);
+        hook_context.tag = Some(actual_tag_name.clone());
+        self.run_hook(HookPoint::PostTag, &hook_context);
+        self.run_hook(HookPoint::PostFlow, &hook_context);
+        warn!("{}", tr!(MergeMasterReminder));
+        success!("{}", tr!(SpecificReleaseCompleted, actual_tag_name));
     }
 
     fn do_release_hotfix(&self) {
-        GitExecution::fetch();
-        GitExecution::switch_branch(&String::from(Self::MASTER_BRANCH));
-        GitExecution::pull();
-        let option_to_release_version_number = GitExecution::get_last_tag_name()
-            .and_then(|last_tag_name| {
-                Util::substring_after(&last_tag_name, "v")
-            })
-            .and_then(|sub| {
-                Util::substring_before(&sub, ".RELEASE")
-            })
-            .map(|last_version_number| {
-                let mut vec = last_version_number.split(".")
-                    .map(|item| String::from(item))
-                    .collect::<Vec<String>>();
-                vec[2] = ((&vec[2].parse::<i32>().unwrap()) + 1).to_string();
-                vec.join(".")
-            });
+        let mut hook_context = HookContext::new(FlowType::Release, Some(ReleaseType::Hotfix));
+        self.run_hook(HookPoint::PreFlow, &hook_context);
+        self.git_backend.fetch();
+        self.git_backend.switch_branch(Self::MASTER_BRANCH);
+        self.git_backend.pull();
+        let option_to_release_version_number = self.config.release.next_hotfix_version_number(self.git_backend.get_last_tag_name(Self::MASTER_BRANCH));
         if option_to_release_version_number.is_none() {
-            e_red_ln!("There is no tag exists in master branch,Hotfix can not release without previous tag");
-            abort();
+            fail(&tr!(NoTagForHotfixRelease));
         }
         let to_release_version_number = option_to_release_version_number.unwrap();
-        let to_release_branch_name = Self::HOTFIX_BRANCH.to_owned().to_owned() + "/" + to_release_version_number.as_str();
-        let remote_branch_exist = GitExecution::is_branch_exists(&to_release_branch_name, true);
-        let local_branch_exist = GitExecution::is_branch_exists(&to_release_branch_name, false);
+        let new_feature_version_number = self.next_development_version_number(&to_release_version_number);
+        let to_release_branch_name = Self::HOTFIX_BRANCH.to_owned() + "/" + to_release_version_number.as_str();
+        hook_context.branch = Some(to_release_branch_name.clone());
+        let remote_branch_exist = self.git_backend.is_branch_exists(&to_release_branch_name, true);
+        let local_branch_exist = self.git_backend.is_branch_exists(&to_release_branch_name, false);
         if remote_branch_exist || local_branch_exist {
-            GitExecution::fetch();
-            GitExecution::switch_branch(&to_release_branch_name);
+            self.git_backend.fetch();
+            self.git_backend.switch_branch(&to_release_branch_name);
             if remote_branch_exist {
-                GitExecution::pull();
+                self.git_backend.pull();
             }
         } else {
-            e_red_ln!("Hotfix branch doesn't exists ,Branch name : {}",to_release_branch_name);
-            abort();
-        }
-        GitExecution::checkout_branch(&String::from(Self::MASTER_BRANCH));
-        GitExecution::pull();
-        GitExecution::merge_to_current(&to_release_branch_name);
-        let release_version = to_release_version_number.to_owned() + ".RELEASE";
-        self.git_flow_action_adapter.modify_new_version(&release_version);
-        let anything_changed = GitExecution::are_there_anything_changed();
+            fail(&tr!(HotfixBranchNotExists, to_release_branch_name));
+        }
+        self.git_backend.checkout_branch(Self::MASTER_BRANCH);
+        self.git_backend.pull();
+        let master_revision = self.revision_of(Self::MASTER_BRANCH);
+        self.git_backend.merge_to_current(&to_release_branch_name);
+        let release_version = to_release_version_number.to_owned() + RELEASE_SUFFIX;
+        self.modify_version(&release_version, &mut hook_context);
+        self.run_pre_release_commit_hook(Self::MASTER_BRANCH, &master_revision, &hook_context);
+        self.verify_release(Self::MASTER_BRANCH, &master_revision);
+        let anything_changed = self.git_backend.are_there_anything_changed();
+        if anything_changed {
+            self.commit(&("release: ".to_owned() + to_release_version_number.as_str()));
+        }
+        let actual_tag_name = VersionUtil::release_tag_name(&to_release_version_number);
+        self.tag(&actual_tag_name);
+        hook_context.tag = Some(actual_tag_name.clone());
+        self.run_hook(HookPoint::PostTag, &hook_context);
+        let next_feature_version = new_feature_version_number.to_owned() + SNAPSHOT_SUFFIX;
+        self.modify_version(&next_feature_version, &mut hook_context);
+        let anything_changed = self.git_backend.are_there_anything_changed();
         if anything_changed {
-            GitExecution::commit("release: ".to_owned() + to_release_version_number.as_str());
-        }
-        let date_str = Utc::now().format("%Y%m%d").to_string();
-        let mut new_version_vec = Vec::new();
-        new_version_vec.push(release_version.as_str());
-        new_version_vec.push(&date_str.as_str());
-        let new_tag_name = new_version_vec.join(".");
-        let actual_tag_name = String::from("v".to_owned() + new_tag_name.as_str());
-        GitExecution::tag(&actual_tag_name);
-        let mut next_version_number_vec = to_release_version_number.split(".")
-            .map(|item| String::from(item))
-            .collect::<Vec<String>>();
-        next_version_number_vec[1] = ((&next_version_number_vec[1].parse::<i32>().unwrap()) + 1).to_string();
-        next_version_number_vec[2] = String::from("0");
-        let new_feature_version_number = next_version_number_vec.join(".");
-        let next_feature_version = new_feature_version_number.to_owned() + "-SNAPSHOT";
-        self.git_flow_action_adapter.modify_new_version(&next_feature_version);
-        let anything_changed = GitExecution::are_there_anything_changed();
+            self.commit(&("back to : ".to_owned() + next_feature_version.as_str()));
+        }
+        self.git_backend.switch_branch(Self::DEVELOP_BRANCH);
+        self.git_backend.pull();
+        self.git_backend.merge_to_current(Self::MASTER_BRANCH);
+        self.run_hook(HookPoint::PostFlow, &hook_context);
+        success!("{}", tr!(HotfixReleaseCompleted, actual_tag_name));
+    }
+
+    fn do_hotfix_from_tag(&self, base_tag_name: String) {
+        let mut hook_context = HookContext::new(FlowType::Hotfix, None);
+        self.run_hook(HookPoint::PreFlow, &hook_context);
+        self.git_backend.fetch();
+        let new_version_number = self.hotfix_version_number_of_tag(&base_tag_name);
+        let new_branch_name = Self::HOTFIX_BRANCH.to_owned() + "/" + new_version_number.as_str();
+        hook_context.branch = Some(new_branch_name.clone());
+        let remote_branch_exists = self.git_backend.is_branch_exists(&new_branch_name, true);
+        let local_branch_exists = self.git_backend.is_branch_exists(&new_branch_name, false);
+        if remote_branch_exists || local_branch_exists {
+            self.git_backend.switch_branch(&new_branch_name);
+            if remote_branch_exists {
+                self.git_backend.pull();
+            }
+            self.run_hook(HookPoint::PostFlow, &hook_context);
+            success!("{}", tr!(HotfixBranchExists, new_branch_name));
+            return;
+        }
+        self.git_backend.force_branch(&new_branch_name, &base_tag_name);
+        self.git_backend.switch_branch(&new_branch_name);
+        let new_version = new_version_number.to_owned() + SNAPSHOT_SUFFIX;
+        self.modify_version(&new_version, &mut hook_context);
+        let anything_changed = self.git_backend.are_there_anything_changed();
         if anything_changed {
-            GitExecution::commit("back to : ".to_owned() + next_feature_version.as_str());
+            self.commit(&("new hotfix: ".to_owned() + new_version_number.as_str()))
         }
-        GitExecution::switch_branch(&String::from(Self::DEVELOP_BRANCH));
-        GitExecution::pull();
-        GitExecution::merge_to_current(&String::from(Self::MASTER_BRANCH));
-        e_green_ln!("Hotfix release flow execution completed,Release tag name : {}" ,actual_tag_name);
+        self.run_hook(HookPoint::PostFlow, &hook_context);
+        success!("{}", tr!(HotfixFlowCompleted, new_branch_name));
     }
-}
\ No newline at end of file
+
+    fn do_release_hotfix_from_tag(&self, base_tag_name: String) {
+        let mut hook_context = HookContext::new(FlowType::Release, Some(ReleaseType::Hotfix));
+        self.run_hook(HookPoint::PreFlow, &hook_context);
+        self.git_backend.fetch();
+        let to_release_version_number = self.hotfix_version_number_of_tag(&base_tag_name);
+        let to_release_branch_name = Self::HOTFIX_BRANCH.to_owned() + "/" + to_release_version_number.as_str();
+        hook_context.branch = Some(to_release_branch_name.clone());
+        let remote_branch_exist = self.git_backend.is_branch_exists(&to_release_branch_name, true);
+        let local_branch_exist = self.git_backend.is_branch_exists(&to_release_branch_name, false);
+        if remote_branch_exist || local_branch_exist {
+            self.git_backend.switch_branch(&to_release_branch_name);
+            if remote_branch_exist {
+                self.git_backend.pull();
+            }
+        } else {
+            fail(&tr!(HotfixBranchNotExists, to_release_branch_name));
+        }
+        let branch_revision = self.revision_of(&to_release_branch_name);
+        let release_version = to_release_version_number.to_owned() + RELEASE_SUFFIX;
+        self.modify_version(&release_version, &mut hook_context);
+        self.run_pre_release_commit_hook(&to_release_branch_name, &branch_revision, &hook_context);
+        self.verify_release(&to_release_branch_name, &branch_revision);
+        let anything_changed = self.git_backend.are_there_anything_changed();
+        if anything_changed {
+            self.commit(&("release: ".to_owned() + to_release_version_number.as_str()));
+        }
+        let actual_tag_name = VersionUtil::release_tag_name(&to_release_version_number);
+        self.tag(&actual_tag_name);
+        hook_context.tag = Some(actual_tag_name.clone());
+        self.run_hook(HookPoint::PostTag, &hook_context);
+        self.run_hook(HookPoint::PostFlow, &hook_context);
+        success!("{}", tr!(TagHotfixReleaseCompleted, actual_tag_name));
+    }
+
+    fn do_support(&self, base_tag_name: String) {
+        let mut hook_context = HookContext::new(FlowType::Support, None);
+        self.run_hook(HookPoint::PreFlow, &hook_context);
+        self.git_backend.fetch();
+        let option_line = VersionUtil::version_number_of_tag(&base_tag_name)
+            .and_then(|version_number| self.config.release.hotfix_line_of(&version_number));
+        let line = match option_line {
+            Some(line) => line,
+            None => fail(&tr!(InvalidReleaseTag, base_tag_name)),
+        };
+        let new_branch_name = Self::SUPPORT_BRANCH.to_owned() + "/" + line.as_str();
+        hook_context.branch = Some(new_branch_name.clone());
+        let remote_branch_exists = self.git_backend.is_branch_exists(&new_branch_name, true);
+        let local_branch_exists = self.git_backend.is_branch_exists(&new_branch_name, false);
+        if remote_branch_exists || local_branch_exists {
+            self.switch_and_pull(&new_branch_name);
+            self.run_hook(HookPoint::PostFlow, &hook_context);
+            success!("{}", tr!(SupportBranchExists, new_branch_name));
+            return;
+        }
+        self.git_backend.force_branch(&new_branch_name, &base_tag_name);
+        self.git_backend.switch_branch(&new_branch_name);
+        self.run_hook(HookPoint::PostFlow, &hook_context);
+        success!("{}", tr!(SupportFlowCompleted, new_branch_name, base_tag_name));
+    }
+
+    fn do_hotfix_on_support(&self, support_branch_name: String) {
+        let mut hook_context = HookContext::new(FlowType::Hotfix, None);
+        self.run_hook(HookPoint::PreFlow, &hook_context);
+        self.git_backend.fetch();
+        self.switch_and_pull(&support_branch_name);
+        let new_version_number = self.hotfix_version_number_of_support(&support_branch_name);
+        let new_branch_name = Self::HOTFIX_BRANCH.to_owned() + "/" + new_version_number.as_str();
+        hook_context.branch = Some(new_branch_name.clone());
+        let remote_branch_exists = self.git_backend.is_branch_exists(&new_branch_name, true);
+        let local_branch_exists = self.git_backend.is_branch_exists(&new_branch_name, false);
+        if remote_branch_exists || local_branch_exists {
+            self.switch_and_pull(&new_branch_name);
+            self.run_hook(HookPoint::PostFlow, &hook_context);
+            success!("{}", tr!(HotfixBranchExists, new_branch_name));
+            return;
+        }
+        self.git_backend.create_new_branch_from_current(&new_branch_name);
+        self.git_backend.checkout_branch(&new_branch_name);
+        let new_version = new_version_number.to_owned() + SNAPSHOT_SUFFIX;
+        self.modify_version(&new_version, &mut hook_context);
+        let anything_changed = self.git_backend.are_there_anything_changed();
+        if anything_changed {
+            self.commit(&("new hotfix: ".to_owned() + new_version_number.as_str()))
+        }
+        self.run_hook(HookPoint::PostFlow, &hook_context);
+        success!("{}", tr!(HotfixFlowCompleted, new_branch_name));
+    }
+
+    fn do_release_hotfix_on_support(&self, support_branch_name: String) {
+        let mut hook_context = HookContext::new(FlowType::Release, Some(ReleaseType::Hotfix));
+        self.run_hook(HookPoint::PreFlow, &hook_context);
+        self.git_backend.fetch();
+        let to_release_version_number = self.hotfix_version_number_of_support(&support_branch_name);
+        let to_release_branch_name = Self::HOTFIX_BRANCH.to_owned() + "/" + to_release_version_number.as_str();
+        hook_context.branch = Some(to_release_branch_name.clone());
+        let remote_branch_exist = self.git_backend.is_branch_exists(&to_release_branch_name, true);
+        let local_branch_exist = self.git_backend.is_branch_exists(&to_release_branch_name, false);
+        if remote_branch_exist || local_branch_exist {
+            self.switch_and_pull(&to_release_branch_name);
+        } else {
+            fail(&tr!(HotfixBranchNotExists, to_release_branch_name));
+        }
+        self.switch_and_pull(&support_branch_name);
+        let support_revision = self.revision_of(&support_branch_name);
+        self.git_backend.merge_to_current(&to_release_branch_name);
+        let release_version = to_release_version_number.to_owned() + RELEASE_SUFFIX;
+        self.modify_version(&release_version, &mut hook_context);
+        self.run_pre_release_commit_hook(&support_branch_name, &support_revision, &hook_context);
+        self.verify_release(&support_branch_name, &support_revision);
+        let anything_changed = self.git_backend.are_there_anything_changed();
+        if anything_changed {
+            self.commit(&("release: ".to_owned() + to_release_version_number.as_str()));
+        }
+        let actual_tag_name = VersionUtil::release_tag_name(&to_release_version_number);
+        self.tag(&actual_tag_name);
+        hook_context.tag = Some(actual_tag_name.clone());
+        self.run_hook(HookPoint::PostTag, &hook_context);
+        self.run_hook(HookPoint::PostFlow, &hook_context);
+        success!("{}", tr!(SupportHotfixReleaseCompleted, actual_tag_name, support_branch_name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use chrono::Utc;
+
+    use crate::action::common_git_flow_action::CommonGitFlowAction;
+    use crate::action::memory_git_flow_action_adapter::MemoryGitFlowActionAdapter;
+    use crate::execution::memory_git_backend::{MemoryGitBackend, MemoryRepository};
+    use crate::flow::git_flow::GitFlow;
+    use crate::support::config::GitFlowConfig;
+    use crate::support::enums::{VerificationLevel, VersionScheme};
+
+    /// master released as 1.2.0 and moved back to 1.3.0-SNAPSHOT, develop branched from master
+    fn released_repository() -> MemoryRepository {
+        MemoryRepository::new("1.2.0-SNAPSHOT")
+            .with_commit("master", "release: 1.2.0", "1.2.0.RELEASE")
+            .with_tag("v1.2.0.RELEASE.20240101", "master")
+            .with_commit("master", "back to : 1.3.0-SNAPSHOT", "1.3.0-SNAPSHOT")
+            .with_branch("develop", "master")
+    }
+
+    fn git_flow_action(repository: &Rc<RefCell<MemoryRepository>>) -> Box<CommonGitFlowAction> {
+        CommonGitFlowAction::new(
+            Box::new(MemoryGitFlowActionAdapter::new(repository.clone())),
+            Box::new(MemoryGitBackend::new(repository.clone())),
+            GitFlowConfig::default(),
+        )
+    }
+
+    fn tag_name_of(version: &str) -> String {
+        format!("v{}.RELEASE.{}", version, Utc::now().format("%Y%m%d"))
+    }
+
+    #[test]
+    fn feature_creates_branch_from_develop() {
+        let repository = released_repository().with_everything_pushed().shared();
+        git_flow_action(&repository).do_feature();
+        let repository = repository.borrow();
+        assert_eq!("feature/1.3.0", repository.current_branch);
+        assert_eq!(repository.branch_head("develop"), repository.branch_head("feature/1.3.0"));
+        assert_eq!("1.3.0-SNAPSHOT", repository.working_version);
+    }
+
+    #[test]
+    fn feature_switches_to_existing_remote_branch() {
+        let repository = released_repository()
+            .with_branch("feature/1.3.0", "develop")
+            .with_commit("feature/1.3.0", "some feature", "1.3.0-SNAPSHOT")
+            .with_everything_pushed()
+            .with_remote_only("feature/1.3.0")
+            .shared();
+        git_flow_action(&repository).do_feature();
+        let repository = repository.borrow();
+        assert_eq!("feature/1.3.0", repository.current_branch);
+        assert_eq!("some feature", repository.head_commit("feature/1.3.0").message);
+    }
+
+    #[test]
+    fn hotfix_creates_branch_from_master_with_next_patch_version() {
+        let repository = released_repository().with_everything_pushed().shared();
+        git_flow_action(&repository).do_hotfix();
+        let repository = repository.borrow();
+        assert_eq!("hotfix/1.2.1", repository.current_branch);
+        let head = repository.head_commit("hotfix/1.2.1");
+        assert_eq!("new hotfix: 1.2.1", head.message);
+        assert_eq!("1.2.1-SNAPSHOT", head.version);
+        assert_eq!(vec![repository.branch_head("master")], head.parents);
+    }
+
+    #[test]
+    fn hotfix_ignores_tags_unreachable_from_master_or_not_released() {
+        let repository = released_repository()
+            .with_branch("support/1.9", "master")
+            .with_commit("support/1.9", "release: 1.9.0", "1.9.0.RELEASE")
+            .with_tag("v1.9.0.RELEASE.20240301", "support/1.9")
+            .with_tag("nightly", "master")
+            .with_everything_pushed()
+            .shared();
+        git_flow_action(&repository).do_hotfix();
+        assert_eq!("hotfix/1.2.1", repository.borrow().current_branch);
+    }
+
+    #[test]
+    fn hotfix_switches_to_existing_local_branch() {
+        let repository = released_repository()
+            .with_branch("hotfix/1.2.1", "master")
+            .with_commit("hotfix/1.2.1", "new hotfix: 1.2.1", "1.2.1-SNAPSHOT")
+            .with_everything_pushed()
+            .shared();
+        let commit_count = repository.borrow().commits.len();
+        git_flow_action(&repository).do_hotfix();
+        let repository = repository.borrow();
+        assert_eq!("hotfix/1.2.1", repository.current_branch);
+        assert_eq!(commit_count, repository.commits.len());
+    }
+
+    #[test]
+    fn release_test_tags_master_and_starts_next_feature() {
+        let repository = released_repository()
+            .with_branch("test/1.3.0", "develop")
+            .with_commit("test/1.3.0", "some feature", "1.3.0-SNAPSHOT")
+            .with_everything_pushed()
+            .shared();
+        git_flow_action(&repository).do_release_test();
+        let repository = repository.borrow();
+        assert_eq!(vec![(String::from("1.3.0.RELEASE"), VerificationLevel::Build)], repository.verifications);
+        let tag_commit = repository.tag_commit(&tag_name_of("1.3.0")).unwrap();
+        assert_eq!("release: 1.3.0", tag_commit.message);
+        assert_eq!("1.3.0.RELEASE", tag_commit.version);
+        assert!(repository.is_ancestor(repository.branch_head("test/1.3.0"), tag_commit.id));
+        let master_head = repository.head_commit("master");
+        assert_eq!("back to : 1.4.0-SNAPSHOT", master_head.message);
+        assert_eq!("1.4.0-SNAPSHOT", master_head.version);
+        assert!(repository.is_ancestor(master_head.id, repository.branch_head("develop")));
+        assert_eq!("1.4.0-SNAPSHOT", repository.head_commit("develop").version);
+        assert_eq!("feature/1.4.0", repository.current_branch);
+        assert_eq!(repository.branch_head("develop"), repository.branch_head("feature/1.4.0"));
+    }
+
+    #[test]
+    fn major_release_continues_numbering_from_major() {
+        let repository = released_repository().with_everything_pushed().shared();
+        let git_flow_action = git_flow_action(&repository);
+        git_flow_action.do_major_feature();
+        {
+            let repository = repository.borrow();
+            assert_eq!("feature/2.0.0", repository.current_branch);
+            assert_eq!("new feature: 2.0.0", repository.head_commit("feature/2.0.0").message);
+            assert_eq!("2.0.0-SNAPSHOT", repository.head_commit("feature/2.0.0").version);
+            assert_eq!("1.3.0-SNAPSHOT", repository.head_commit("develop").version);
+        }
+        git_flow_action.git_backend.create_new_branch_from_current("test/2.0.0");
+        git_flow_action.do_release_major();
+        {
+            let repository = repository.borrow();
+            assert_eq!("2.0.0.RELEASE", repository.tag_commit(&tag_name_of("2.0.0")).unwrap().version);
+            assert_eq!("2.1.0-SNAPSHOT", repository.head_commit("develop").version);
+            assert_eq!("feature/2.1.0", repository.current_branch);
+        }
+        git_flow_action.do_hotfix();
+        assert_eq!("hotfix/2.0.1", repository.borrow().current_branch);
+    }
+
+    #[test]
+    fn release_test_records_promoted_candidate() {
+        let repository = released_repository()
+            .with_branch("test/1.3.0", "develop")
+            .with_commit("test/1.3.0", "some feature", "1.3.0-SNAPSHOT")
+            .with_everything_pushed()
+            .shared();
+        let git_flow_action = git_flow_action(&repository);
+        git_flow_action.do_release_candidate(false);
+        git_flow_action.do_release_candidate(false);
+        {
+            let repository = repository.borrow();
+            assert_eq!("test/1.3.0", repository.current_branch);
+            assert_eq!("1.3.0-RC.1", repository.tag_commit("v1.3.0-RC.1").unwrap().version);
+            let candidate_commit = repository.tag_commit("v1.3.0-RC.2").unwrap();
+            assert_eq!("release candidate: 1.3.0-RC.2", candidate_commit.message);
+            assert_eq!("1.3.0-RC.2", candidate_commit.version);
+            assert_eq!(vec![candidate_commit.id], repository.head_commit("test/1.3.0").parents);
+            assert_eq!("1.3.0-SNAPSHOT", repository.head_commit("test/1.3.0").version);
+        }
+        git_flow_action.do_release_test();
+        let repository = repository.borrow();
+        let tag_commit = repository.tag_commit(&tag_name_of("1.3.0")).unwrap();
+        assert_eq!("release: 1.3.0\n\npromoted from v1.3.0-RC.2", tag_commit.message);
+        assert_eq!("1.3.0.RELEASE", tag_commit.version);
+    }
+
+    #[test]
+    fn release_hotfix_tags_master_and_merges_into_develop() {
+        let repository = released_repository()
+            .with_branch("hotfix/1.2.1", "master")
+            .with_commit("hotfix/1.2.1", "new hotfix: 1.2.1", "1.2.1-SNAPSHOT")
+            .with_commit("hotfix/1.2.1", "fix", "1.2.1-SNAPSHOT")
+            .with_everything_pushed()
+            .shared();
+        git_flow_action(&repository).do_release_hotfix();
+        let repository = repository.borrow();
+        let tag_commit = repository.tag_commit(&tag_name_of("1.2.1")).unwrap();
+        assert_eq!("release: 1.2.1", tag_commit.message);
+        assert_eq!("1.2.1.RELEASE", tag_commit.version);
+        assert_eq!("1.3.0-SNAPSHOT", repository.head_commit("master").version);
+        assert!(repository.is_ancestor(repository.branch_head("master"), repository.branch_head("develop")));
+        assert_eq!("1.3.0-SNAPSHOT", repository.head_commit("develop").version);
+        assert_eq!("develop", repository.current_branch);
+    }
+
+    #[test]
+    fn calendar_versions_for_hotfix_and_release() {
+        // released in January 2020 and the test branch of the next release created before the month is over
+        let repository = MemoryRepository::new("1.0.0-SNAPSHOT")
+            .with_commit("master", "release", "2020.1.0.RELEASE")
+            .with_tag(&tag_name_of("2020.1.0"), "master")
+            .with_commit("master", "back to", "2020.1.1-SNAPSHOT")
+            .with_branch("develop", "master")
+            .with_branch("test/2020.1.1", "develop")
+            .with_commit("test/2020.1.1", "some feature", "2020.1.1-SNAPSHOT")
+            .with_everything_pushed()
+            .shared();
+        let mut git_flow_action = git_flow_action(&repository);
+        git_flow_action.config.release.version_scheme = VersionScheme::CalVer;
+        git_flow_action.do_hotfix();
+        assert_eq!("hotfix/2020.1.0.1", repository.borrow().current_branch);
+        git_flow_action.do_release_hotfix();
+        assert!(repository.borrow().tag_commit(&tag_name_of("2020.1.0.1")).is_some());
+        git_flow_action.do_hotfix();
+        assert_eq!("hotfix/2020.1.0.2", repository.borrow().current_branch);
+        git_flow_action.do_release_test();
+        let repository = repository.borrow();
+        let tag_commit = repository.tag_commit(&tag_name_of("2020.1.1")).unwrap();
+        assert_eq!("2020.1.1.RELEASE", tag_commit.version);
+        assert!(repository.is_ancestor(repository.branch_head("test/2020.1.1"), tag_commit.id));
+    }
+
+    #[test]
+    fn release_without_verification_when_skipped() {
+        let repository = released_repository()
+            .with_branch("hotfix/1.2.1", "master")
+            .with_commit("hotfix/1.2.1", "new hotfix: 1.2.1", "1.2.1-SNAPSHOT")
+            .with_everything_pushed()
+            .shared();
+        let mut config = GitFlowConfig::default();
+        config.release.verification = VerificationLevel::Skip;
+        let git_flow_action = CommonGitFlowAction::new(
+            Box::new(MemoryGitFlowActionAdapter::new(repository.clone())),
+            Box::new(MemoryGitBackend::new(repository.clone())),
+            config,
+        );
+        git_flow_action.do_release_hotfix();
+        let repository = repository.borrow();
+        assert!(repository.tag_commit(&tag_name_of("1.2.1")).is_some());
+        assert!(repository.verifications.is_empty());
+    }
+
+    /// 1.3.0 released after 1.2.0, master moved to 1.4.0-SNAPSHOT
+    fn released_twice_repository() -> MemoryRepository {
+        released_repository()
+            .with_commit("master", "release: 1.3.0", "1.3.0.RELEASE")
+            .with_tag("v1.3.0.RELEASE.20240201", "master")
+            .with_commit("master", "back to : 1.4.0-SNAPSHOT", "1.4.0-SNAPSHOT")
+    }
+
+    #[test]
+    fn hotfix_from_tag_branches_from_the_tag() {
+        let repository = released_twice_repository().with_everything_pushed().shared();
+        git_flow_action(&repository).do_hotfix_from_tag(String::from("v1.2.0.RELEASE.20240101"));
+        let repository = repository.borrow();
+        assert_eq!("hotfix/1.2.1", repository.current_branch);
+        let head = repository.head_commit("hotfix/1.2.1");
+        assert_eq!("1.2.1-SNAPSHOT", head.version);
+        assert_eq!(vec![repository.tag_commit("v1.2.0.RELEASE.20240101").unwrap().id], head.parents);
+    }
+
+    #[test]
+    fn release_hotfix_from_tag_does_not_touch_master_and_develop() {
+        let repository = released_twice_repository().with_everything_pushed().shared();
+        git_flow_action(&repository).do_hotfix_from_tag(String::from("v1.2.0.RELEASE.20240101"));
+        let master = repository.borrow().branch_head("master");
+        let develop = repository.borrow().branch_head("develop");
+        git_flow_action(&repository).do_release_hotfix_from_tag(String::from("v1.2.0.RELEASE.20240101"));
+        let repository = repository.borrow();
+        let tag_commit = repository.tag_commit(&tag_name_of("1.2.1")).unwrap();
+        assert_eq!("release: 1.2.1", tag_commit.message);
+        assert_eq!("1.2.1.RELEASE", tag_commit.version);
+        assert_eq!(repository.branch_head("hotfix/1.2.1"), tag_commit.id);
+        assert_eq!(master, repository.branch_head("master"));
+        assert_eq!(develop, repository.branch_head("develop"));
+        assert_eq!("hotfix/1.2.1", repository.current_branch);
+    }
+
+    #[test]
+    fn support_hotfix_is_released_into_support_branch() {
+        let repository = released_twice_repository().with_everything_pushed().shared();
+        let master = repository.borrow().branch_head("master");
+        let develop = repository.borrow().branch_head("develop");
+        git_flow_action(&repository).do_support(String::from("v1.2.0.RELEASE.20240101"));
+        {
+            let repository = repository.borrow();
+            assert_eq!("support/1.2", repository.current_branch);
+            assert_eq!(repository.tag_commit("v1.2.0.RELEASE.20240101").unwrap().id, repository.branch_head("support/1.2"));
+        }
+        git_flow_action(&repository).do_hotfix_on_support(String::from("support/1.2"));
+        {
+            let repository = repository.borrow();
+            assert_eq!("hotfix/1.2.1", repository.current_branch);
+            assert_eq!(vec![repository.branch_head("support/1.2")], repository.head_commit("hotfix/1.2.1").parents);
+        }
+        git_flow_action(&repository).do_release_hotfix_on_support(String::from("support/1.2"));
+        let repository = repository.borrow();
+        let tag_commit = repository.tag_commit(&tag_name_of("1.2.1")).unwrap();
+        assert_eq!("1.2.1.RELEASE", tag_commit.version);
+        assert_eq!(repository.branch_head("support/1.2"), tag_commit.id);
+        assert!(repository.is_ancestor(repository.branch_head("hotfix/1.2.1"), tag_commit.id));
+        assert_eq!(master, repository.branch_head("master"));
+        assert_eq!(develop, repository.branch_head("develop"));
+        assert_eq!("support/1.2", repository.current_branch);
+    }
+
+    #[test]
+    fn calendar_hotfixes_from_tag_and_on_support() {
+        let repository = MemoryRepository::new("1.0.0-SNAPSHOT")
+            .with_commit("master", "release: 2020.1.0", "2020.1.0.RELEASE")
+            .with_tag("v2020.1.0.RELEASE.20200110", "master")
+            .with_commit("master", "release: 2020.1.0.1", "2020.1.0.1.RELEASE")
+            .with_tag("v2020.1.0.1.RELEASE.20200115", "master")
+            .with_commit("master", "release: 2020.1.1", "2020.1.1.RELEASE")
+            .with_tag("v2020.1.1.RELEASE.20200120", "master")
+            .with_branch("develop", "master")
+            .with_everything_pushed()
+            .shared();
+        let mut git_flow_action = git_flow_action(&repository);
+        git_flow_action.config.release.version_scheme = VersionScheme::CalVer;
+        git_flow_action.do_hotfix_from_tag(String::from("v2020.1.0.1.RELEASE.20200115"));
+        assert_eq!("hotfix/2020.1.0.2", repository.borrow().current_branch);
+        git_flow_action.do_release_hotfix_from_tag(String::from("v2020.1.0.1.RELEASE.20200115"));
+        assert_eq!("2020.1.0.2.RELEASE", repository.borrow().tag_commit(&tag_name_of("2020.1.0.2")).unwrap().version);
+        git_flow_action.do_support(String::from("v2020.1.0.RELEASE.20200110"));
+        assert_eq!("support/2020.1.0", repository.borrow().current_branch);
+        git_flow_action.do_hotfix_on_support(String::from("support/2020.1.0"));
+        assert_eq!("hotfix/2020.1.0.3", repository.borrow().current_branch);
+        git_flow_action.do_release_hotfix_on_support(String::from("support/2020.1.0"));
+        let repository = repository.borrow();
+        assert_eq!(repository.branch_head("support/2020.1.0"), repository.tag_commit(&tag_name_of("2020.1.0.3")).unwrap().id);
+    }
+
+    #[test]
+    fn release_specific_tags_snapshot_version_of_branch() {
+        let repository = released_repository()
+            .with_branch("feature/1.3.0", "develop")
+            .with_commit("feature/1.3.0", "some feature", "1.3.0-SNAPSHOT")
+            .with_everything_pushed()
+            .shared();
+        git_flow_action(&repository).do_release_specific(String::from("feature/1.3.0"));
+        let repository = repository.borrow();
+        let tag_commit = repository.tag_commit(&tag_name_of("1.3.0")).unwrap();
+        assert_eq!("1.3.0.RELEASE", tag_commit.version);
+        assert_eq!(repository.branch_head("master"), tag_commit.id);
+        assert_eq!("master", repository.current_branch);
+    }
+}
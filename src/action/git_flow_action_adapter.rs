@@ -1,16 +1,33 @@
-use crate::support::enums::ProjectType;
+use crate::execution::git_backend::GitBackend;
+use crate::support::enums::{ProjectType, VerificationLevel};
 
 pub trait GitFlowActionAdapter {
 
     // Current project type
     fn current_project_type(&self) -> ProjectType;
 
-    /// Verify current project
-    fn verify_project(&self);
+    /// Verify current project by building it, and running its tests for [VerificationLevel::Test],
+    /// the error describes the step which failed
+    fn verify_project(&self, verification_level: &VerificationLevel) -> Result<(), String>;
 
     /// Gets current project version
     fn get_current_project_version(&self) -> Option<String>;
 
     /// Modify new version
-    fn modify_new_version(&self,new_version: &String);
+    fn modify_new_version(&self,new_version: &str);
+
+    /// The shell command modifying the version, suggested to fix versions by hand
+    fn modify_version_command(&self, new_version: &str) -> String;
+
+    /// The file holding the project version
+    fn version_file_name(&self) -> &'static str;
+
+    /// Extract project version from the content of version file
+    fn extract_project_version(&self, content: &str) -> Option<String>;
+
+    /// Gets project version at revision without switching branch
+    fn get_project_version_at(&self, git_backend: &dyn GitBackend, revision: &str) -> Option<String> {
+        git_backend.read_file(revision, self.version_file_name())
+            .and_then(|content| self.extract_project_version(&content))
+    }
 }
\ No newline at end of file
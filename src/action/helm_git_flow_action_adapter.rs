@@ -0,0 +1,254 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use log::info;
+use regex::Regex;
+use serde_norway::Value;
+
+use crate::action::git_flow_action_adapter::GitFlowActionAdapter;
+use crate::support::enums::{ProjectType, VerificationLevel};
+use crate::support::failure::fail;
+use crate::support::version::{RELEASE_SUFFIX, VersionUtil};
+use crate::tr;
+
+/// The top level `version: ...` line of Chart.yaml, the value may be quoted
+static VERSION_LINE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?m)^(version:[ \t]*["']?)([^"'\s#]+)(["']?)"#).unwrap());
+
+/// The top level `appVersion: ...` line of Chart.yaml, the value may be quoted
+static APP_VERSION_LINE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?m)^(appVersion:[ \t]*["']?)([^"'\s#]+)(["']?)"#).unwrap());
+
+/// A SemVer 2 version, the only versions Helm packages and installs, https://semver.org
+static SEMVER_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(
+    r"^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)(-(0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*)(\.(0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*))*)?(\+[0-9a-zA-Z-]+(\.[0-9a-zA-Z-]+)*)?$"
+).unwrap());
+
+const CHART_FILE: &str = "Chart.yaml";
+
+const VALUES_FILE: &str = "values.yaml";
+
+const SUBCHART_DIR: &str = "charts";
+
+const API_VERSIONS: [&str; 2] = ["v1", "v2"];
+
+const REQUIRED_FIELDS: [&str; 3] = ["apiVersion", "name", "version"];
+
+/// A chart released with its Chart.yaml version, the subcharts under `charts/` having the
+/// same version are released with it, and appVersion follows the version when they are equal.
+/// Release versions are written as plain `1.3.0`, `1.3.0.RELEASE` is not a version Helm accepts
+pub struct HelmGitFlowActionAdapter {}
+
+impl HelmGitFlowActionAdapter {
+    /// Checks Chart.yaml and values.yaml of the chart and of every unpacked subchart
+    fn lint_chart(chart_dir: &Path) -> Result<(), String> {
+        let chart_file = chart_dir.join(CHART_FILE);
+        let content = fs::read_to_string(&chart_file)
+            .map_err(|_| tr!(ChartYamlNotFound, chart_file.display()))?;
+        let chart = Self::parse_yaml(&chart_file, &content)?;
+        for field in REQUIRED_FIELDS {
+            if chart.get(field).and_then(Value::as_str).is_none_or(|value| value.is_empty()) {
+                return Err(tr!(ChartFieldMissing, chart_file.display(), field));
+            }
+        }
+        let api_version = chart.get("apiVersion").and_then(Value::as_str).unwrap_or_default();
+        if !API_VERSIONS.contains(&api_version) {
+            return Err(tr!(ChartApiVersionInvalid, chart_file.display(), api_version));
+        }
+        let version = chart.get("version").and_then(Value::as_str).unwrap_or_default();
+        if !SEMVER_REGEX.is_match(version) {
+            return Err(tr!(ChartVersionInvalid, chart_file.display(), version));
+        }
+        let values_file = chart_dir.join(VALUES_FILE);
+        if let Ok(values) = fs::read_to_string(&values_file) {
+            Self::parse_yaml(&values_file, &values)?;
+        }
+        Self::subchart_dirs_of(chart_dir)
+            .iter()
+            .try_for_each(|subchart_dir| Self::lint_chart(subchart_dir))
+    }
+
+    fn parse_yaml(path: &Path, content: &str) -> Result<Value, String> {
+        serde_norway::from_str::<Value>(content)
+            .map_err(|err| tr!(ParseYamlFailed, path.display(), err))
+    }
+
+    /// The unpacked subcharts, packed `.tgz` ones are released on their own
+    fn subchart_dirs_of(chart_dir: &Path) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(chart_dir.join(SUBCHART_DIR)) else {
+            return Vec::new();
+        };
+        let mut subchart_dirs = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.join(CHART_FILE).is_file())
+            .collect::<Vec<PathBuf>>();
+        subchart_dirs.sort();
+        subchart_dirs
+    }
+
+    /// The version written into Chart.yaml, `1.3.0.RELEASE` to `1.3.0`
+    fn chart_version_of(version: &str) -> &str {
+        version.strip_suffix(RELEASE_SUFFIX).unwrap_or(version)
+    }
+
+    /// Replaces the version, and appVersion when it is the same, keeping the other lines untouched
+    fn replace_version(content: &str, new_version: &str) -> Option<String> {
+        let version = VERSION_LINE_REGEX.captures(content)
+            .map(|captures| String::from(&captures[2]))?;
+        let replacement = "${1}".to_owned() + new_version + "${3}";
+        let new_content = VERSION_LINE_REGEX.replace(content, replacement.as_str());
+        let follows_version = APP_VERSION_LINE_REGEX.captures(&new_content)
+            .is_some_and(|captures| captures[2] == version);
+        if follows_version {
+            return Some(APP_VERSION_LINE_REGEX.replace(&new_content, replacement.as_str()).into_owned());
+        }
+        Some(new_content.into_owned())
+    }
+}
+
+impl GitFlowActionAdapter for HelmGitFlowActionAdapter {
+    fn current_project_type(&self) -> ProjectType {
+        ProjectType::Helm
+    }
+
+    fn verify_project(&self, _verification_level: &VerificationLevel) -> Result<(), String> {
+        info!("{}", tr!(HelmVerify));
+        Self::lint_chart(Path::new("."))
+    }
+
+    fn get_current_project_version(&self) -> Option<String> {
+        info!("{}", tr!(HelmGetVersion));
+        let content = fs::read_to_string(self.version_file_name()).ok()?;
+        self.extract_project_version(&content)
+    }
+
+    fn modify_new_version(&self, new_version: &str) {
+        info!("{}", tr!(HelmUpdateVersion, new_version));
+        let chart_file = PathBuf::from(self.version_file_name());
+        let content = fs::read_to_string(&chart_file)
+            .unwrap_or_else(|_| fail(&tr!(ChartYamlNotFound, chart_file.display())));
+        let Some(version) = self.extract_project_version(&content) else {
+            fail(&tr!(ChartFieldMissing, chart_file.display(), "version"));
+        };
+        let mut chart_files = vec![chart_file];
+        for subchart_dir in Self::subchart_dirs_of(Path::new(".")) {
+            let subchart_file = subchart_dir.join(CHART_FILE);
+            let released_with_chart = fs::read_to_string(&subchart_file).ok()
+                .and_then(|content| self.extract_project_version(&content))
+                .is_some_and(|subchart_version| subchart_version == version);
+            if released_with_chart {
+                chart_files.push(subchart_file);
+            }
+        }
+        for chart_file in chart_files {
+            let content = fs::read_to_string(&chart_file)
+                .unwrap_or_else(|_| fail(&tr!(ChartYamlNotFound, chart_file.display())));
+            let Some(new_content) = Self::replace_version(&content, Self::chart_version_of(new_version)) else {
+                fail(&tr!(ChartFieldMissing, chart_file.display(), "version"));
+            };
+            if let Err(err) = fs::write(&chart_file, new_content) {
+                fail(&tr!(WriteFileFailed, chart_file.display(), err));
+            }
+        }
+    }
+
+    /// Replaces the version lines like [HelmGitFlowActionAdapter::modify_new_version], the charts
+    /// and appVersion lines having the current version of Chart.yaml are the ones moved with it.
+    /// The charts are rewritten through a temporary file, `sed -i` differs between GNU and BSD
+    fn modify_version_command(&self, new_version: &str) -> String {
+        format!(
+            r#"version=$(sed -n -E "s/^version:[[:blank:]]*[\"']?([^\"'[:blank:]#]+).*/\1/p" {chart} | sed 's/[.]/[.]/g') && for chart in {chart} {subchart_dir}/*/{chart}; do if grep -q -E "^version:[[:blank:]]*[\"']?$version([\"'[:blank:]#]|$)" "$chart" 2>/dev/null; then sed -E "s/^((version|appVersion):[[:blank:]]*[\"']?)$version([\"'[:blank:]#]|$)/\1{new_version}\3/" "$chart" > "$chart.tmp" && mv "$chart.tmp" "$chart"; fi; done"#,
+            chart = CHART_FILE,
+            subchart_dir = SUBCHART_DIR,
+            new_version = Self::chart_version_of(new_version),
+        )
+    }
+
+    fn version_file_name(&self) -> &'static str {
+        CHART_FILE
+    }
+
+    /// A plain version number of the chart is a release version, `1.3.0` to `1.3.0.RELEASE`
+    fn extract_project_version(&self, content: &str) -> Option<String> {
+        let version = VERSION_LINE_REGEX.captures(content)
+            .map(|captures| String::from(&captures[2]))?;
+        if VersionUtil::is_version_number(&version) {
+            return Some(version + RELEASE_SUFFIX);
+        }
+        Some(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::process::Command;
+
+    use crate::action::git_flow_action_adapter::GitFlowActionAdapter;
+    use crate::action::helm_git_flow_action_adapter::HelmGitFlowActionAdapter;
+
+    const CHART: &str = "# the chart of the service\napiVersion: v2\nname: service\nversion: 1.3.0-SNAPSHOT # bumped by the flow\nappVersion: \"1.3.0-SNAPSHOT\"\n";
+
+    #[test]
+    fn replace_version_keeps_comments_and_order() {
+        let new_content = HelmGitFlowActionAdapter::replace_version(CHART, "1.3.0.RELEASE").unwrap();
+        assert_eq!(CHART.replace("1.3.0-SNAPSHOT", "1.3.0.RELEASE"), new_content);
+        let pinned = CHART.replace("\"1.3.0-SNAPSHOT\"", "'7.0.5'");
+        let new_content = HelmGitFlowActionAdapter::replace_version(&pinned, "1.3.0.RELEASE").unwrap();
+        assert!(new_content.contains("version: 1.3.0.RELEASE # bumped by the flow\nappVersion: '7.0.5'\n"));
+        assert_eq!(None, HelmGitFlowActionAdapter::replace_version("name: service\n", "1.3.0"));
+    }
+
+    #[test]
+    fn lint_chart_and_subcharts() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let chart_dir = temp_dir.path();
+        fs::write(chart_dir.join("Chart.yaml"), CHART).unwrap();
+        fs::create_dir_all(chart_dir.join("charts").join("redis")).unwrap();
+        fs::write(chart_dir.join("charts").join("redis").join("Chart.yaml"), "apiVersion: v3\nname: redis\nversion: 7.0.5\n").unwrap();
+        let message = HelmGitFlowActionAdapter::lint_chart(chart_dir).unwrap_err();
+        assert!(message.contains("v3"), "{}", message);
+        fs::write(chart_dir.join("charts").join("redis").join("Chart.yaml"), "apiVersion: v2\nname: redis\nversion: 7.0.5\n").unwrap();
+        HelmGitFlowActionAdapter::lint_chart(chart_dir).unwrap();
+        fs::write(chart_dir.join("values.yaml"), "image: [").unwrap();
+        assert!(HelmGitFlowActionAdapter::lint_chart(chart_dir).is_err());
+        fs::remove_file(chart_dir.join("values.yaml")).unwrap();
+        fs::write(chart_dir.join("Chart.yaml"), CHART.replace("1.3.0-SNAPSHOT", "1.3.0.RELEASE")).unwrap();
+        let message = HelmGitFlowActionAdapter::lint_chart(chart_dir).unwrap_err();
+        assert!(message.contains("1.3.0.RELEASE"), "{}", message);
+    }
+
+    #[test]
+    fn release_version_is_plain_semver() {
+        let adapter = HelmGitFlowActionAdapter {};
+        assert_eq!("1.3.0", HelmGitFlowActionAdapter::chart_version_of("1.3.0.RELEASE"));
+        assert_eq!("1.3.0-SNAPSHOT", HelmGitFlowActionAdapter::chart_version_of("1.3.0-SNAPSHOT"));
+        assert_eq!(Some(String::from("1.3.0.RELEASE")), adapter.extract_project_version("version: 1.3.0\n"));
+        assert_eq!(Some(String::from("1.3.0-RC.1")), adapter.extract_project_version("version: 1.3.0-RC.1\n"));
+    }
+
+    #[test]
+    fn modify_version_command_moves_what_the_flow_moves() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let chart_dir = temp_dir.path();
+        let worker = "apiVersion: v2\nname: worker\nversion: '1.3.0-SNAPSHOT'\n";
+        let redis = "apiVersion: v2\nname: redis\nversion: 1.3.0-SNAPSHOTS\nappVersion: \"1.3.0-SNAPSHOT\"\n";
+        fs::write(chart_dir.join("Chart.yaml"), CHART).unwrap();
+        for (name, content) in [("worker", worker), ("redis", redis)] {
+            fs::create_dir_all(chart_dir.join("charts").join(name)).unwrap();
+            fs::write(chart_dir.join("charts").join(name).join("Chart.yaml"), content).unwrap();
+        }
+        let command = HelmGitFlowActionAdapter {}.modify_version_command("1.3.0.RELEASE");
+        let status = Command::new("sh").arg("-c").arg(&command).current_dir(chart_dir).status().unwrap();
+        assert!(status.success(), "{}", command);
+
+        let chart = fs::read_to_string(chart_dir.join("Chart.yaml")).unwrap();
+        assert_eq!(HelmGitFlowActionAdapter::replace_version(CHART, "1.3.0"), Some(chart));
+        let worker_chart = fs::read_to_string(chart_dir.join("charts").join("worker").join("Chart.yaml")).unwrap();
+        assert_eq!(worker.replace("1.3.0-SNAPSHOT", "1.3.0"), worker_chart);
+        let redis_chart = fs::read_to_string(chart_dir.join("charts").join("redis").join("Chart.yaml")).unwrap();
+        assert_eq!(redis, redis_chart);
+    }
+}
@@ -1,6 +1,18 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
 use crate::action::git_flow_action_adapter::GitFlowActionAdapter;
 use crate::execution::maven_execution::MavenExecution;
-use crate::support::enums::ProjectType;
+use crate::support::enums::{ProjectType, VerificationLevel};
+
+/// The `<parent>` block of pom.xml, whose version is not the project's
+static PARENT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<parent>.*?</parent>").unwrap());
+
+/// The first `<version>` of pom.xml once the parent block is removed
+static VERSION_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<version>\s*([^<\s]+)\s*</version>").unwrap());
 
 pub struct MavenGitFlowActionAdapter {}
 
@@ -10,16 +22,30 @@ impl GitFlowActionAdapter for MavenGitFlowActionAdapter {
         ProjectType::Maven
     }
 
-    fn verify_project(&self) {
-        MavenExecution::check_maven_command_exist();
-        MavenExecution::verify_project();
+    fn verify_project(&self, verification_level: &VerificationLevel) -> Result<(), String> {
+        MavenExecution::check_maven_command_exist()?;
+        MavenExecution::verify_project(VerificationLevel::Test.eq(verification_level))
     }
 
     fn get_current_project_version(&self) -> Option<String> {
         MavenExecution::get_current_project_version()
     }
 
-    fn modify_new_version(&self, new_version: &String) {
+    fn modify_new_version(&self, new_version: &str) {
         MavenExecution::update_version_to(new_version)
     }
-}
\ No newline at end of file
+
+    fn modify_version_command(&self, new_version: &str) -> String {
+        format!("mvn versions:set -DnewVersion={} -DgenerateBackupPoms=false", new_version)
+    }
+
+    fn version_file_name(&self) -> &'static str {
+        "pom.xml"
+    }
+
+    fn extract_project_version(&self, content: &str) -> Option<String> {
+        let without_parent = PARENT_REGEX.replace(content, "");
+        VERSION_REGEX.captures(&without_parent)
+            .map(|captures| String::from(&captures[1]))
+    }
+}
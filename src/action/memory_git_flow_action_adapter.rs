@@ -0,0 +1,50 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::action::git_flow_action_adapter::GitFlowActionAdapter;
+use crate::execution::memory_git_backend::MemoryRepository;
+use crate::support::enums::{ProjectType, VerificationLevel};
+
+/// Project adapter reading and writing the version file of a [MemoryRepository]
+pub struct MemoryGitFlowActionAdapter {
+    pub repository: Rc<RefCell<MemoryRepository>>,
+}
+
+impl MemoryGitFlowActionAdapter {
+    pub fn new(repository: Rc<RefCell<MemoryRepository>>) -> MemoryGitFlowActionAdapter {
+        MemoryGitFlowActionAdapter { repository }
+    }
+}
+
+impl GitFlowActionAdapter for MemoryGitFlowActionAdapter {
+    fn current_project_type(&self) -> ProjectType {
+        ProjectType::Maven
+    }
+
+    fn verify_project(&self, verification_level: &VerificationLevel) -> Result<(), String> {
+        let mut repository = self.repository.borrow_mut();
+        let verification = (repository.working_version.clone(), verification_level.clone());
+        repository.verifications.push(verification);
+        Ok(())
+    }
+
+    fn get_current_project_version(&self) -> Option<String> {
+        Some(self.repository.borrow().working_version.clone())
+    }
+
+    fn modify_new_version(&self, new_version: &str) {
+        self.repository.borrow_mut().working_version = String::from(new_version);
+    }
+
+    fn modify_version_command(&self, new_version: &str) -> String {
+        format!("echo {} > version", new_version)
+    }
+
+    fn version_file_name(&self) -> &'static str {
+        "version"
+    }
+
+    fn extract_project_version(&self, content: &str) -> Option<String> {
+        Some(String::from(content))
+    }
+}
@@ -1,4 +1,6 @@
 pub mod common_git_flow_action;
 pub mod git_flow_action_adapter;
 pub mod maven_git_flow_action_adapter;
-pub mod webpack_git_flow_action_adapter;
\ No newline at end of file
+pub mod webpack_git_flow_action_adapter;
+pub mod helm_git_flow_action_adapter;
+pub mod memory_git_flow_action_adapter;
@@ -1,98 +1,96 @@
 use std::fs;
-use std::fs::{File, OpenOptions};
-use std::io::{BufRead, BufReader};
-use std::process::abort;
+use std::path::Path;
+use std::sync::LazyLock;
 
-use colour::{e_blue_ln, e_red_ln};
+use log::{info, warn};
+use regex::Regex;
+use serde_json::Value;
 
 use crate::action::git_flow_action_adapter::GitFlowActionAdapter;
-use crate::support::enums::ProjectType;
+use crate::execution::node_execution::NodeExecution;
+use crate::support::enums::{ProjectType, VerificationLevel};
+use crate::support::failure::fail;
+use crate::tr;
+
+/// The top level `"version": "..."` line of package.json
+static VERSION_LINE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?m)^(\s*"version"\s*:\s*")([^"]*)(")"#).unwrap());
+
+const BUILD_SCRIPT: &str = "build";
+
+const TEST_SCRIPT: &str = "test";
 
 pub struct WebpackGitFlowActionAdapter {}
 
+impl WebpackGitFlowActionAdapter {
+    /// The names of `scripts` in package.json
+    fn scripts_of(content: &str) -> Result<Vec<String>, String> {
+        let package_json = serde_json::from_str::<Value>(content)
+            .map_err(|err| tr!(ParsePackageJsonFailed, err))?;
+        let scripts = package_json.get("scripts")
+            .and_then(Value::as_object)
+            .map(|scripts| scripts.keys().cloned().collect::<Vec<String>>())
+            .unwrap_or_default();
+        Ok(scripts)
+    }
+}
+
 impl GitFlowActionAdapter for WebpackGitFlowActionAdapter {
     fn current_project_type(&self) -> ProjectType {
         ProjectType::Webpack
     }
 
-    fn verify_project(&self) {
-        e_blue_ln!("[Verify]Verify Webpack project ...");
-        let paths = fs::read_dir("./").unwrap();
-        let mut package_json_file_path: Option<String> = None;
-        for path in paths {
-            let temp = String::from(path.unwrap().file_name().to_str().unwrap());
-            if temp.ends_with("package.json") {
-                package_json_file_path = Option::Some(temp);
-            }
+    fn verify_project(&self, verification_level: &VerificationLevel) -> Result<(), String> {
+        info!("{}", tr!(WebpackVerify));
+        let content = fs::read_to_string(self.version_file_name())
+            .map_err(|_| tr!(PackageJsonNotFound))?;
+        let scripts = Self::scripts_of(&content)?;
+        if !scripts.iter().any(|script| script == BUILD_SCRIPT) {
+            return Err(tr!(ScriptNotFound, BUILD_SCRIPT));
         }
-        if package_json_file_path.is_none() {
-            e_red_ln!("Current directory does not has a package.json file,please check work dir");
-            abort();
+        let project_dir = Path::new(".");
+        let package_manager = NodeExecution::detect_package_manager(project_dir);
+        NodeExecution::check_command_exist(&package_manager)?;
+        NodeExecution::clean_install(&package_manager, project_dir)?;
+        NodeExecution::run_script(&package_manager, BUILD_SCRIPT)?;
+        if VerificationLevel::Test.eq(verification_level) {
+            if scripts.iter().any(|script| script == TEST_SCRIPT) {
+                NodeExecution::run_script(&package_manager, TEST_SCRIPT)?;
+            } else {
+                warn!("{}", tr!(TestScriptNotFound, TEST_SCRIPT));
+            }
         }
+        Ok(())
     }
 
     fn get_current_project_version(&self) -> Option<String> {
-        e_blue_ln!("[Webpack]Get current project's version");
-        let paths = fs::read_dir("./").unwrap();
-        let mut package_json_file_path: Option<String> = None;
-        for path in paths {
-            let temp = String::from(path.unwrap().file_name().to_str().unwrap());
-            if temp.ends_with("package.json") {
-                package_json_file_path = Option::Some(temp);
-            }
-        }
-        if package_json_file_path.is_none() {
-            return None;
-        }
-        let f = BufReader::new(File::open("./".to_owned() + package_json_file_path.unwrap().as_str()).unwrap());
-        let it = f.lines()
-            .map(|line| line.unwrap())
-            .filter(|line| line.starts_with("\"version\":"))
-            .next();
-        if it.is_none() {
-            return None;
-        }
-        let line = it.unwrap();
-        let start_bytes = line.find("\"version\": \"");
-        let end_bytes = line.find("\",");
-        if start_bytes.is_none() || end_bytes.is_none() {
-            return None;
-        }
-        let result = &line[start_bytes.unwrap()..end_bytes.unwrap()];
-        Some(String::from(result))
+        info!("{}", tr!(WebpackGetVersion));
+        let content = fs::read_to_string(self.version_file_name()).ok()?;
+        self.extract_project_version(&content)
     }
 
-    fn modify_new_version(&self, new_version: &String) {
-        e_blue_ln!("[Webpack]Update project's version to new version : {}",new_version);
-        let paths = fs::read_dir("./").unwrap();
-        let mut package_json_file_path: Option<String> = None;
-        for path in paths {
-            let temp = String::from(path.unwrap().file_name().to_str().unwrap());
-            if temp.ends_with("package.json") {
-                package_json_file_path = Option::Some(temp);
-            }
+    fn modify_new_version(&self, new_version: &str) {
+        info!("{}", tr!(WebpackUpdateVersion, new_version));
+        let package_json_file_path = self.version_file_name();
+        let content = fs::read_to_string(package_json_file_path)
+            .unwrap_or_else(|_| fail(&tr!(PackageJsonNotFound)));
+        let replacement = "${1}".to_owned() + new_version + "${3}";
+        let new_content = VERSION_LINE_REGEX.replace(content.as_str(), replacement.as_str());
+        if let Err(err) = fs::write(package_json_file_path, new_content.as_bytes()) {
+            fail(&tr!(WriteFileFailed, package_json_file_path, err));
         }
-        if package_json_file_path.is_none() {
-            e_red_ln!("Current directory does not has a package.json file,please check work dir");
-            abort();
-        }
-        let package_json_file_path: String = "./".to_owned() + package_json_file_path.unwrap().as_str();
-        let file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .open(package_json_file_path.clone())
-            .expect("package.json doesn't exist or so");
-        let lines = BufReader::new(file)
-            .lines()
-            .map(|line| {
-                let string = line.unwrap();
-                if string.trim().starts_with("\"version\":") {
-                    return "    \"version\": \"".to_owned() + new_version.as_str() + "\",";
-                }
-                string
-            })
-            .collect::<Vec<String>>()
-            .join("\n");
-        fs::write(package_json_file_path.clone().as_str(), lines).expect("Can't write lines to file");
     }
-}
\ No newline at end of file
+
+    fn modify_version_command(&self, new_version: &str) -> String {
+        format!("npm pkg set version={}", new_version)
+    }
+
+    fn version_file_name(&self) -> &'static str {
+        "package.json"
+    }
+
+    fn extract_project_version(&self, content: &str) -> Option<String> {
+        VERSION_LINE_REGEX.captures(content)
+            .map(|captures| String::from(&captures[2]))
+    }
+}
@@ -0,0 +1,138 @@
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+
+use log::{info, warn};
+use tabled::{Style, Table, Tabled};
+use version_compare::{compare_to, Cmp};
+
+use crate::action::common_git_flow_action::CommonGitFlowAction;
+use crate::command::{is_flow_branch, reference_of};
+use crate::execution::git_backend::GitBackend;
+use crate::flow::git_flow::GitFlow;
+use crate::support::failure::fail;
+use crate::support::prompt::select_branches_to_delete;
+use crate::support::util::Util;
+use crate::support::version::VersionUtil;
+use crate::success;
+use crate::tr;
+
+/// Deletes the flow branches which are already released
+pub struct CleanupCommand {
+    pub git_backend: Box<dyn GitBackend>,
+}
+
+impl CleanupCommand {
+    pub fn new(git_backend: Box<dyn GitBackend>) -> CleanupCommand {
+        CleanupCommand { git_backend }
+    }
+
+    pub fn execute(&self, dry_run: bool, select_all: bool) {
+        let candidates = self.find_candidates();
+        if candidates.is_empty() {
+            success!("{}", tr!(NothingToCleanUp));
+            return;
+        }
+        info!("{}", Table::new(&candidates).with(Style::modern()));
+        if dry_run {
+            for candidate in &candidates {
+                if candidate.local {
+                    warn!("{}", tr!(DryRunDeleteBranch, candidate.branch));
+                }
+                if candidate.remote {
+                    warn!("{}", tr!(DryRunDeleteRemoteBranch, candidate.branch));
+                }
+            }
+            return;
+        }
+        let branch_names = candidates.iter()
+            .map(|candidate| candidate.branch.clone())
+            .collect::<Vec<String>>();
+        let selected_branch_names = if select_all {
+            branch_names
+        } else {
+            match select_branches_to_delete(branch_names) {
+                Ok(selected) => selected,
+                Err(_) => fail(&tr!(InvalidBranchesToDelete)),
+            }
+        };
+        let current_branch_name = self.git_backend.get_current_branch_name();
+        for candidate in candidates.iter().filter(|candidate| selected_branch_names.contains(&candidate.branch)) {
+            if candidate.local {
+                if current_branch_name.as_ref() == Some(&candidate.branch) {
+                    warn!("{}", tr!(CannotDeleteCurrentBranch, candidate.branch));
+                } else {
+                    self.git_backend.delete_branch(&candidate.branch);
+                }
+            }
+            if candidate.remote {
+                self.git_backend.delete_remote_branch(&candidate.branch);
+            }
+        }
+        success!("{}", tr!(CleanupCompleted, selected_branch_names.join(", ")));
+    }
+
+    /// Flow branches merged into master, or whose version is not greater than the latest release
+    fn find_candidates(&self) -> Vec<CleanupCandidate> {
+        self.git_backend.fetch();
+        let local_branches = self.git_backend.list_all_branch().into_iter().collect::<BTreeSet<String>>();
+        let remote_branches = self.git_backend.list_remote_branch().into_iter().collect::<BTreeSet<String>>();
+        let master_reference = reference_of(CommonGitFlowAction::MASTER_BRANCH, &local_branches);
+        let last_release_version_number = self.git_backend.get_last_tag_name(&master_reference)
+            .and_then(|tag_name| VersionUtil::version_number_of_tag(&tag_name));
+        local_branches.union(&remote_branches)
+            .filter(|branch_name| is_flow_branch(branch_name))
+            .filter_map(|branch_name| {
+                let reference = reference_of(branch_name, &local_branches);
+                let merged = self.git_backend.get_ahead_behind(&reference, &master_reference)
+                    .map(|(ahead, _)| ahead == 0)
+                    .unwrap_or(false);
+                let released = Util::substring_after(branch_name, "/")
+                    .zip(last_release_version_number.as_ref())
+                    .map(|(version_number, last_version_number)| {
+                        compare_to(version_number, last_version_number, Cmp::Le).unwrap_or(false)
+                    })
+                    .unwrap_or(false);
+                let reason = match (merged, released) {
+                    (true, _) => tr!(ReasonMergedIntoMaster),
+                    (false, true) => tr!(ReasonVersionReleased),
+                    _ => return None,
+                };
+                Some(CleanupCandidate {
+                    branch: branch_name.to_string(),
+                    local: local_branches.contains(branch_name),
+                    remote: remote_branches.contains(branch_name),
+                    reason,
+                })
+            })
+            .collect::<Vec<CleanupCandidate>>()
+    }
+}
+
+struct CleanupCandidate {
+    branch: String,
+    local: bool,
+    remote: bool,
+    reason: String,
+}
+
+impl Tabled for CleanupCandidate {
+    const LENGTH: usize = 4;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![
+            Cow::Borrowed(&self.branch),
+            Cow::Owned(self.local.to_string()),
+            Cow::Owned(self.remote.to_string()),
+            Cow::Borrowed(&self.reason),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Owned(tr!(HeaderBranch)),
+            Cow::Owned(tr!(HeaderLocal)),
+            Cow::Owned(tr!(HeaderRemote)),
+            Cow::Owned(tr!(HeaderReason)),
+        ]
+    }
+}
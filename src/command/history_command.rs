@@ -0,0 +1,116 @@
+use std::borrow::Cow;
+
+use tabled::{Style, Table, Tabled};
+
+use crate::execution::git_backend::GitBackend;
+use crate::support::audit::{AuditLog, AuditRecord, Outcome, RefChange};
+use crate::success;
+use crate::tr;
+
+const UNKNOWN: &str = "-";
+
+/// Prints the audit log of flow executions, the latest first
+pub struct HistoryCommand {
+    pub git_backend: Box<dyn GitBackend>,
+}
+
+impl HistoryCommand {
+    pub fn new(git_backend: Box<dyn GitBackend>) -> HistoryCommand {
+        HistoryCommand { git_backend }
+    }
+
+    pub fn execute(&self, limit: Option<usize>) {
+        let records = AuditLog::read_all(self.git_backend.as_ref());
+        if records.is_empty() {
+            success!("{}", tr!(NoAuditRecord));
+            return;
+        }
+        let history_contents = records.iter()
+            .rev()
+            .take(limit.unwrap_or(usize::MAX))
+            .map(HistoryContent::of)
+            .collect::<Vec<HistoryContent>>();
+        println!("{}", Table::new(history_contents).with(Style::modern()));
+    }
+}
+
+/// `Release(Test)` for the release flows, the flow type for the others, `Undo Release(Test)` for the undo of them
+pub fn flow_name_of(record: &AuditRecord) -> String {
+    let flow_name = match &record.parameters.release_type {
+        Some(release_type) => format!("{}({})", record.parameters.flow_type, release_type),
+        None => record.parameters.flow_type.to_string(),
+    };
+    match record.undo_of {
+        Some(_) => tr!(UndoFlowName, flow_name),
+        None => flow_name,
+    }
+}
+
+/// `+` for created refs, `-` for deleted refs and `~` for moved refs, `refs/heads/develop` is shortened to `develop`
+fn ref_change_of(ref_change: &RefChange) -> String {
+    let short_name = ref_change.name.strip_prefix("refs/heads/")
+        .or_else(|| ref_change.name.strip_prefix("refs/remotes/"))
+        .or_else(|| ref_change.name.strip_prefix("refs/"))
+        .unwrap_or(&ref_change.name);
+    let mark = match (&ref_change.before, &ref_change.after) {
+        (None, _) => "+",
+        (_, None) => "-",
+        _ => "~",
+    };
+    mark.to_owned() + short_name
+}
+
+struct HistoryContent {
+    time: String,
+    user: String,
+    flow: String,
+    original_branch: String,
+    ref_changes: String,
+    outcome: String,
+}
+
+impl HistoryContent {
+    fn of(record: &AuditRecord) -> HistoryContent {
+        HistoryContent {
+            time: record.time.clone(),
+            user: record.user.clone(),
+            flow: flow_name_of(record),
+            original_branch: record.original_branch.clone().unwrap_or(String::from(UNKNOWN)),
+            ref_changes: record.ref_changes()
+                .iter()
+                .map(ref_change_of)
+                .collect::<Vec<String>>()
+                .join("\n"),
+            outcome: match record.outcome {
+                Outcome::Success => tr!(OutcomeSuccess),
+                Outcome::Failure => tr!(OutcomeFailure, record.message.clone().unwrap_or_default()),
+            },
+        }
+    }
+}
+
+impl Tabled for HistoryContent {
+    const LENGTH: usize = 6;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![
+            Cow::Borrowed(&self.time),
+            Cow::Borrowed(&self.user),
+            Cow::Borrowed(&self.flow),
+            Cow::Borrowed(&self.original_branch),
+            Cow::Borrowed(&self.ref_changes),
+            Cow::Borrowed(&self.outcome),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Owned(tr!(HeaderTime)),
+            Cow::Owned(tr!(HeaderUser)),
+            Cow::Owned(tr!(HeaderFlow)),
+            Cow::Owned(tr!(HeaderOriginalBranch)),
+            Cow::Owned(tr!(HeaderRefChanges)),
+            Cow::Owned(tr!(HeaderOutcome)),
+        ]
+    }
+}
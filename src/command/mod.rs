@@ -0,0 +1,30 @@
+use std::collections::BTreeSet;
+
+use crate::action::common_git_flow_action::CommonGitFlowAction;
+use crate::flow::git_flow::GitFlow;
+
+pub mod status_command;
+pub mod cleanup_command;
+pub mod history_command;
+pub mod undo_command;
+pub mod verify_versions_command;
+
+/// Local branch name, or the remote branch when there is no local one
+pub fn reference_of(branch_name: &str, local_branches: &BTreeSet<String>) -> String {
+    if local_branches.contains(branch_name) {
+        String::from(branch_name)
+    } else {
+        "origin/".to_owned() + branch_name
+    }
+}
+
+/// Whether the branch is a feature, hotfix or test branch
+pub fn is_flow_branch(branch_name: &str) -> bool {
+    [
+        CommonGitFlowAction::FEATURE_BRANCH,
+        CommonGitFlowAction::HOTFIX_BRANCH,
+        CommonGitFlowAction::TEST_BRANCH,
+    ]
+    .iter()
+    .any(|prefix| branch_name.starts_with(&(prefix.to_string() + "/")))
+}
@@ -0,0 +1,199 @@
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+
+use tabled::{Style, Table, Tabled};
+
+use crate::action::common_git_flow_action::CommonGitFlowAction;
+use crate::command::{is_flow_branch, reference_of};
+use crate::action::git_flow_action_adapter::GitFlowActionAdapter;
+use crate::execution::git_backend::GitBackend;
+use crate::flow::git_flow::GitFlow;
+use crate::support::config::ReleaseConfig;
+use crate::support::version::{SNAPSHOT_SUFFIX, VersionUtil};
+use crate::tr;
+
+const UNKNOWN: &str = "-";
+
+/// Prints where the repository stands before picking a flow
+pub struct StatusCommand {
+    pub git_flow_action_adapter: Box<dyn GitFlowActionAdapter>,
+    pub git_backend: Box<dyn GitBackend>,
+    pub release_config: ReleaseConfig,
+}
+
+impl StatusCommand {
+    pub fn new(git_flow_action_adapter: Box<dyn GitFlowActionAdapter>, git_backend: Box<dyn GitBackend>, release_config: ReleaseConfig) -> StatusCommand {
+        StatusCommand {
+            git_flow_action_adapter,
+            git_backend,
+            release_config,
+        }
+    }
+
+    pub fn execute(&self) {
+        self.git_backend.fetch();
+        let local_branches = self.git_backend.list_all_branch().into_iter().collect::<BTreeSet<String>>();
+        let remote_branches = self.git_backend.list_remote_branch().into_iter().collect::<BTreeSet<String>>();
+        let master_reference = reference_of(CommonGitFlowAction::MASTER_BRANCH, &local_branches);
+        let develop_reference = reference_of(CommonGitFlowAction::DEVELOP_BRANCH, &local_branches);
+        let last_tag_name = self.git_backend.get_last_tag_name(&master_reference);
+        let develop_version = self.version_at(&develop_reference);
+
+        let overview = vec![
+            StatusContent::new(&tr!(StatusCurrentBranch), self.git_backend.get_current_branch_name()),
+            StatusContent::new(&tr!(StatusCurrentVersion), self.git_flow_action_adapter.get_current_project_version()),
+            StatusContent::new(&tr!(StatusLastTag), last_tag_name.clone()),
+            StatusContent::new(&tr!(StatusMasterVersion), self.version_at(&master_reference)),
+            StatusContent::new(&tr!(StatusDevelopVersion), develop_version.clone()),
+        ];
+        println!("{}", Table::new(overview).with(Style::modern()));
+
+        let branch_contents = local_branches.union(&remote_branches)
+            .filter(|branch_name| is_flow_branch(branch_name))
+            .map(|branch_name| {
+                let reference = reference_of(branch_name, &local_branches);
+                let location = match (local_branches.contains(branch_name), remote_branches.contains(branch_name)) {
+                    (true, true) => tr!(LocationLocal) + ", " + tr!(LocationOrigin).as_str(),
+                    (true, false) => tr!(LocationLocal),
+                    _ => tr!(LocationOrigin),
+                };
+                BranchContent {
+                    branch: branch_name.to_string(),
+                    location,
+                    version: self.version_at(&reference).unwrap_or(String::from(UNKNOWN)),
+                    master: self.ahead_behind(&reference, &master_reference),
+                    develop: self.ahead_behind(&reference, &develop_reference),
+                }
+            })
+            .collect::<Vec<BranchContent>>();
+        println!("{}", Table::new(branch_contents).with(Style::modern()));
+
+        let feature_version_number = develop_version
+            .and_then(|version| VersionUtil::version_number_of_snapshot(&version));
+        let hotfix_version_number = self.release_config.next_hotfix_version_number(last_tag_name.clone());
+        let test_prefix = CommonGitFlowAction::TEST_BRANCH.to_owned() + "/";
+        let test_version_numbers = local_branches.union(&remote_branches)
+            .filter_map(|branch_name| branch_name.strip_prefix(test_prefix.as_str()).map(String::from))
+            .collect::<Vec<String>>();
+        let release_test_version_number = self.release_config.next_release_version_number_of_test_branches(last_tag_name, &test_version_numbers);
+        let next_versions = vec![
+            NextVersionContent::new(
+                "Feature",
+                feature_version_number.clone().map(|number| number + SNAPSHOT_SUFFIX),
+                feature_version_number.map(|number| CommonGitFlowAction::FEATURE_BRANCH.to_owned() + "/" + number.as_str()),
+            ),
+            NextVersionContent::new(
+                "Hotfix",
+                hotfix_version_number.clone().map(|number| number + SNAPSHOT_SUFFIX),
+                hotfix_version_number.clone().map(|number| CommonGitFlowAction::HOTFIX_BRANCH.to_owned() + "/" + number.as_str()),
+            ),
+            NextVersionContent::new(
+                "Release Test",
+                Some(release_test_version_number.clone()),
+                Some(VersionUtil::release_tag_name(&release_test_version_number)),
+            ),
+            NextVersionContent::new(
+                "Release Hotfix",
+                hotfix_version_number.clone(),
+                hotfix_version_number.map(|number| VersionUtil::release_tag_name(&number)),
+            ),
+        ];
+        println!("{}", Table::new(next_versions).with(Style::modern()));
+    }
+
+    fn version_at(&self, reference: &str) -> Option<String> {
+        self.git_flow_action_adapter.get_project_version_at(self.git_backend.as_ref(), reference)
+    }
+
+    fn ahead_behind(&self, reference: &str, base: &str) -> String {
+        self.git_backend.get_ahead_behind(reference, base)
+            .map(|(ahead, behind)| format!("↑{} ↓{}", ahead, behind))
+            .unwrap_or(String::from(UNKNOWN))
+    }
+}
+
+struct StatusContent {
+    option: String,
+    value: String,
+}
+
+impl Tabled for StatusContent {
+    const LENGTH: usize = 2;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![Cow::Borrowed(&self.option), Cow::Borrowed(&self.value)]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![Cow::Owned(tr!(HeaderOption)), Cow::Owned(tr!(HeaderValue))]
+    }
+}
+
+impl StatusContent {
+    fn new(option: &str, value: Option<String>) -> StatusContent {
+        StatusContent {
+            option: String::from(option),
+            value: value.unwrap_or(String::from(UNKNOWN)),
+        }
+    }
+}
+
+struct BranchContent {
+    branch: String,
+    location: String,
+    version: String,
+    master: String,
+    develop: String,
+}
+
+impl Tabled for BranchContent {
+    const LENGTH: usize = 5;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![
+            Cow::Borrowed(&self.branch),
+            Cow::Borrowed(&self.location),
+            Cow::Borrowed(&self.version),
+            Cow::Borrowed(&self.master),
+            Cow::Borrowed(&self.develop),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Owned(tr!(HeaderBranch)),
+            Cow::Owned(tr!(HeaderLocation)),
+            Cow::Owned(tr!(HeaderVersion)),
+            Cow::Borrowed(CommonGitFlowAction::MASTER_BRANCH),
+            Cow::Borrowed(CommonGitFlowAction::DEVELOP_BRANCH),
+        ]
+    }
+}
+
+struct NextVersionContent {
+    flow: String,
+    version: String,
+    branch_or_tag: String,
+}
+
+impl Tabled for NextVersionContent {
+    const LENGTH: usize = 3;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![Cow::Borrowed(&self.flow), Cow::Borrowed(&self.version), Cow::Borrowed(&self.branch_or_tag)]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![Cow::Owned(tr!(HeaderFlow)), Cow::Owned(tr!(HeaderVersion)), Cow::Owned(tr!(HeaderBranchOrTag))]
+    }
+}
+
+impl NextVersionContent {
+    fn new(flow: &str, version: Option<String>, branch_or_tag: Option<String>) -> NextVersionContent {
+        NextVersionContent {
+            flow: String::from(flow),
+            version: version.unwrap_or(String::from(UNKNOWN)),
+            branch_or_tag: branch_or_tag.unwrap_or(String::from(UNKNOWN)),
+        }
+    }
+}
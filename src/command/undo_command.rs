@@ -0,0 +1,200 @@
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+
+use log::{error, info};
+use tabled::{Style, Table, Tabled};
+
+use crate::command::history_command::flow_name_of;
+use crate::execution::git_backend::GitBackend;
+use crate::support::audit::{AuditLog, AuditRecord, Outcome, RefChange};
+use crate::support::failure::fail;
+use crate::support::prompt::confirm_execution_prompt;
+use crate::success;
+use crate::tr;
+
+const BRANCH_PREFIX: &str = "refs/heads/";
+const TAG_PREFIX: &str = "refs/tags/";
+
+/// Restores the branches and tags changed by the last flow execution
+pub struct UndoCommand {
+    pub git_backend: Box<dyn GitBackend>,
+}
+
+impl UndoCommand {
+    pub fn new(git_backend: Box<dyn GitBackend>) -> UndoCommand {
+        UndoCommand { git_backend }
+    }
+
+    pub fn execute(&self, yes: bool) {
+        let records = AuditLog::read_all(self.git_backend.as_ref());
+        if records.is_empty() {
+            success!("{}", tr!(NoAuditRecord));
+            return;
+        }
+        let Some(record) = record_to_undo(records) else {
+            success!("{}", tr!(NothingToUndo));
+            return;
+        };
+        let ref_changes = undoable_ref_changes_of(&record);
+        self.git_backend.fetch();
+        let problems = self.find_problems(&ref_changes);
+        if !problems.is_empty() {
+            for problem in &problems {
+                error!("{}", problem);
+            }
+            fail(&tr!(UndoRefused, record.time, flow_name_of(&record)));
+        }
+        if self.git_backend.are_there_anything_changed() {
+            fail(&tr!(UndoDirtyWorkingTree));
+        }
+        // without the original branch the undo stays on current branch, which must not be deleted
+        let current_branch_name = self.git_backend.get_current_branch_name();
+        if record.original_branch.is_none() {
+            let deleted_current_branch = ref_changes.iter()
+                .filter(|ref_change| ref_change.before.is_none())
+                .filter_map(|ref_change| ref_change.name.strip_prefix(BRANCH_PREFIX))
+                .find(|branch_name| current_branch_name.as_deref() == Some(*branch_name));
+            if let Some(branch_name) = deleted_current_branch {
+                fail(&tr!(UndoOnDeletedBranch, branch_name));
+            }
+        }
+        let undo_contents = ref_changes.iter()
+            .map(UndoContent::of)
+            .collect::<Vec<UndoContent>>();
+        info!("{}", Table::new(undo_contents).with(Style::modern()));
+        let confirm_result = if yes {
+            Ok(true)
+        } else {
+            confirm_execution_prompt()
+        };
+        if !confirm_result.unwrap_or_default() {
+            fail(&tr!(Cancelled));
+        }
+        AuditLog::start_undo(&record, self.git_backend.as_ref());
+        // the deleted and moved refs are restored first so the original branch exists before switching to it
+        for ref_change in &ref_changes {
+            let Some(before) = &ref_change.before else {
+                continue;
+            };
+            if let Some(branch_name) = ref_change.name.strip_prefix(BRANCH_PREFIX) {
+                self.git_backend.force_branch(branch_name, before);
+            } else if let Some(tag_name) = ref_change.name.strip_prefix(TAG_PREFIX) {
+                self.git_backend.force_tag(tag_name, before);
+            }
+        }
+        if let Some(original_branch) = &record.original_branch {
+            if current_branch_name.as_ref() != Some(original_branch) {
+                self.git_backend.switch_branch(original_branch);
+            }
+        }
+        for ref_change in ref_changes.iter().filter(|ref_change| ref_change.before.is_none()) {
+            if let Some(branch_name) = ref_change.name.strip_prefix(BRANCH_PREFIX) {
+                self.git_backend.delete_branch(branch_name);
+            } else if let Some(tag_name) = ref_change.name.strip_prefix(TAG_PREFIX) {
+                self.git_backend.delete_tag(tag_name);
+            }
+        }
+        AuditLog::finish(self.git_backend.as_ref());
+        match record.original_branch {
+            Some(original_branch) => success!("{}", tr!(UndoCompleted, original_branch)),
+            None => success!("{}", tr!(UndoCompletedOnCurrentBranch, current_branch_name.unwrap_or_default())),
+        }
+    }
+
+    /// The refs which moved since the execution, or whose new commits are already on remote
+    fn find_problems(&self, ref_changes: &[RefChange]) -> Vec<String> {
+        let current_refs = self.git_backend.list_refs();
+        let remote_tags = self.git_backend.list_remote_tags();
+        ref_changes.iter()
+            .filter_map(|ref_change| {
+                if current_refs.get(&ref_change.name) != ref_change.after.as_ref() {
+                    return Some(tr!(UndoRefMoved, ref_change.name));
+                }
+                let after = ref_change.after.as_ref()?;
+                let pushed = if let Some(branch_name) = ref_change.name.strip_prefix(BRANCH_PREFIX) {
+                    let remote_branch_name = "origin/".to_owned() + branch_name;
+                    current_refs.contains_key(&("refs/remotes/".to_owned() + &remote_branch_name))
+                        && self.git_backend.get_ahead_behind(after, &remote_branch_name)
+                            .map(|(ahead, _)| ahead == 0)
+                            .unwrap_or(false)
+                } else {
+                    ref_change.name.strip_prefix(TAG_PREFIX)
+                        .map(|tag_name| remote_tags.iter().any(|remote_tag| remote_tag == tag_name))
+                        .unwrap_or(false)
+                };
+                if pushed {
+                    return Some(tr!(UndoRefPushed, ref_change.name));
+                }
+                None
+            })
+            .collect::<Vec<String>>()
+    }
+}
+
+/// The last execution which changed branches or tags and is not undone yet, failed executions included
+/// as they may have changed refs before failing. The undo records are skipped with the executions they undid
+fn record_to_undo(records: Vec<AuditRecord>) -> Option<AuditRecord> {
+    let mut undone_times = BTreeSet::new();
+    records.into_iter()
+        .rev()
+        .find(|record| {
+            if let Some(undo_of) = &record.undo_of {
+                if Outcome::Success == record.outcome {
+                    undone_times.insert(undo_of.clone());
+                }
+                return false;
+            }
+            !undone_times.contains(&record.time) && !undoable_ref_changes_of(record).is_empty()
+        })
+}
+
+/// The changes of local branches and tags, the remote branches are restored by fetching
+fn undoable_ref_changes_of(record: &AuditRecord) -> Vec<RefChange> {
+    record.ref_changes()
+        .into_iter()
+        .filter(|ref_change| ref_change.name.starts_with(BRANCH_PREFIX) || ref_change.name.starts_with(TAG_PREFIX))
+        .collect::<Vec<RefChange>>()
+}
+
+struct UndoContent {
+    reference: String,
+    before: String,
+    after: String,
+    action: String,
+}
+
+impl UndoContent {
+    fn of(ref_change: &RefChange) -> UndoContent {
+        UndoContent {
+            reference: ref_change.name.clone(),
+            before: ref_change.before.clone().unwrap_or_default(),
+            after: ref_change.after.clone().unwrap_or_default(),
+            action: match &ref_change.before {
+                Some(before) => tr!(UndoActionRestore, before),
+                None => tr!(UndoActionDelete),
+            },
+        }
+    }
+}
+
+impl Tabled for UndoContent {
+    const LENGTH: usize = 4;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![
+            Cow::Borrowed(&self.reference),
+            Cow::Borrowed(&self.before),
+            Cow::Borrowed(&self.after),
+            Cow::Borrowed(&self.action),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Owned(tr!(HeaderRef)),
+            Cow::Owned(tr!(HeaderBefore)),
+            Cow::Owned(tr!(HeaderAfter)),
+            Cow::Owned(tr!(HeaderAction)),
+        ]
+    }
+}
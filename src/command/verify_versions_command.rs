@@ -0,0 +1,210 @@
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+
+use tabled::{Style, Table, Tabled};
+use version_compare::{compare_to, Cmp};
+
+use crate::action::common_git_flow_action::CommonGitFlowAction;
+use crate::action::git_flow_action_adapter::GitFlowActionAdapter;
+use crate::command::reference_of;
+use crate::execution::git_backend::GitBackend;
+use crate::flow::git_flow::GitFlow;
+use crate::support::config::ReleaseConfig;
+use crate::support::failure::fail;
+use crate::support::version::{RELEASE_SUFFIX, SNAPSHOT_SUFFIX, VersionUtil};
+use crate::success;
+use crate::tr;
+
+const UNKNOWN: &str = "-";
+
+/// Cross-checks the versions of branch names, project files, the latest release tag, master and develop
+pub struct VerifyVersionsCommand {
+    pub git_flow_action_adapter: Box<dyn GitFlowActionAdapter>,
+    pub git_backend: Box<dyn GitBackend>,
+    pub release_config: ReleaseConfig,
+}
+
+impl VerifyVersionsCommand {
+    pub fn new(git_flow_action_adapter: Box<dyn GitFlowActionAdapter>, git_backend: Box<dyn GitBackend>, release_config: ReleaseConfig) -> VerifyVersionsCommand {
+        VerifyVersionsCommand {
+            git_flow_action_adapter,
+            git_backend,
+            release_config,
+        }
+    }
+
+    pub fn execute(&self) {
+        let inconsistencies = self.find_inconsistencies();
+        if inconsistencies.is_empty() {
+            success!("{}", tr!(VersionsConsistent));
+            return;
+        }
+        println!("{}", Table::new(&inconsistencies).with(Style::modern()));
+        fail(&tr!(VersionsInconsistent, inconsistencies.len()));
+    }
+
+    fn find_inconsistencies(&self) -> Vec<Inconsistency> {
+        self.git_backend.fetch();
+        let local_branches = self.git_backend.list_all_branch().into_iter().collect::<BTreeSet<String>>();
+        let remote_branches = self.git_backend.list_remote_branch().into_iter().collect::<BTreeSet<String>>();
+        let current_branch_name = self.git_backend.get_current_branch_name();
+        let master_reference = reference_of(CommonGitFlowAction::MASTER_BRANCH, &local_branches);
+        let option_last_tag_name = self.git_backend.get_last_tag_name(&master_reference);
+        let last_version_number = option_last_tag_name.as_ref()
+            .and_then(|tag_name| VersionUtil::version_number_of_tag(tag_name));
+        let next_development_version = last_version_number.as_ref()
+            .and_then(|version_number| self.release_config.next_development_version_number(version_number))
+            .map(|version_number| version_number + SNAPSHOT_SUFFIX);
+        let version_of = |branch_name: &str| -> Option<String> {
+            if current_branch_name.as_deref() == Some(branch_name) {
+                self.git_flow_action_adapter.get_current_project_version()
+            } else {
+                self.git_flow_action_adapter.get_project_version_at(
+                    self.git_backend.as_ref(),
+                    &reference_of(branch_name, &local_branches),
+                )
+            }
+        };
+        let set_version_fix = |branch_name: &str, version: &str| -> String {
+            let modify_version_command = self.git_flow_action_adapter.modify_version_command(version);
+            let fix = format!("{} && git commit -am \"fix version : {}\"", modify_version_command, version);
+            if current_branch_name.as_deref() == Some(branch_name) {
+                fix
+            } else {
+                format!("git switch {} && {}", branch_name, fix)
+            }
+        };
+        let mut inconsistencies = Vec::new();
+
+        // the release commit is tagged, so the tag holds the release version,
+        // the tag may be published already, so which commit to tag again is left to the user
+        if let (Some(last_tag_name), Some(last_version_number)) = (&option_last_tag_name, &last_version_number) {
+            let tag_version = self.git_flow_action_adapter.get_project_version_at(self.git_backend.as_ref(), last_tag_name);
+            let expected_version = last_version_number.to_owned() + RELEASE_SUFFIX;
+            if tag_version.as_ref() != Some(&expected_version) {
+                let fix = tr!(TagVersionManualFix, expected_version, last_tag_name, last_tag_name);
+                inconsistencies.push(Inconsistency::new(last_tag_name, tag_version, expected_version, fix));
+            }
+        }
+
+        // master holds the last release or moved on to the next development version
+        let master_version = version_of(CommonGitFlowAction::MASTER_BRANCH);
+        let master_version_number = master_version.as_ref()
+            .and_then(|version| VersionUtil::version_number_of_snapshot(version));
+        if let (Some(last_version_number), Some(next_development_version)) = (&last_version_number, &next_development_version) {
+            let released = master_version.as_ref() == Some(&(last_version_number.to_owned() + RELEASE_SUFFIX));
+            if !released && !is_after(&master_version_number, last_version_number) {
+                inconsistencies.push(Inconsistency::new(
+                    CommonGitFlowAction::MASTER_BRANCH,
+                    master_version.clone(),
+                    next_development_version.clone(),
+                    set_version_fix(CommonGitFlowAction::MASTER_BRANCH, next_development_version),
+                ));
+            }
+        }
+
+        // develop is developing a version after both the last release and master
+        let develop_version = version_of(CommonGitFlowAction::DEVELOP_BRANCH);
+        let develop_version_number = develop_version.as_ref()
+            .and_then(|version| VersionUtil::version_number_of_snapshot(version));
+        let behind_master = master_version_number.as_ref()
+            .map(|master_version_number| !is_after(&develop_version_number, master_version_number)
+                && develop_version_number.as_ref() != Some(master_version_number))
+            .unwrap_or(false);
+        if behind_master {
+            inconsistencies.push(Inconsistency::new(
+                CommonGitFlowAction::DEVELOP_BRANCH,
+                develop_version.clone(),
+                master_version.clone().unwrap_or_default(),
+                format!("git switch {} && git merge {}", CommonGitFlowAction::DEVELOP_BRANCH, CommonGitFlowAction::MASTER_BRANCH),
+            ));
+        } else if let (Some(last_version_number), Some(next_development_version)) = (&last_version_number, &next_development_version) {
+            if !is_after(&develop_version_number, last_version_number) {
+                inconsistencies.push(Inconsistency::new(
+                    CommonGitFlowAction::DEVELOP_BRANCH,
+                    develop_version.clone(),
+                    next_development_version.clone(),
+                    set_version_fix(CommonGitFlowAction::DEVELOP_BRANCH, next_development_version),
+                ));
+            }
+        }
+
+        // the flow branches are named by the version they develop
+        for branch_name in local_branches.union(&remote_branches) {
+            let Some((prefix, version_number)) = branch_name.split_once('/') else {
+                continue;
+            };
+            if !VersionUtil::is_version_number(version_number) {
+                continue;
+            }
+            let branch_version = version_of(branch_name);
+            if [CommonGitFlowAction::FEATURE_BRANCH, CommonGitFlowAction::HOTFIX_BRANCH, CommonGitFlowAction::TEST_BRANCH].contains(&prefix) {
+                let expected_version = version_number.to_owned() + SNAPSHOT_SUFFIX;
+                if branch_version.as_ref() != Some(&expected_version) {
+                    let fix = set_version_fix(branch_name, &expected_version);
+                    inconsistencies.push(Inconsistency::new(branch_name, branch_version, expected_version, fix));
+                }
+            } else if prefix == CommonGitFlowAction::SUPPORT_BRANCH {
+                let in_line = branch_version.as_ref()
+                    .map(|version| version.trim_end_matches(SNAPSHOT_SUFFIX).trim_end_matches(RELEASE_SUFFIX))
+                    .and_then(|version| self.release_config.hotfix_line_of(version))
+                    .map(|line| line == version_number)
+                    .unwrap_or(false);
+                let option_hotfix_version_number = self.release_config.next_hotfix_version_number_in_line(version_number, &self.git_backend.list_tags());
+                if let (false, Some(hotfix_version_number)) = (in_line, option_hotfix_version_number) {
+                    let expected_version = hotfix_version_number + SNAPSHOT_SUFFIX;
+                    let fix = set_version_fix(branch_name, &expected_version);
+                    inconsistencies.push(Inconsistency::new(branch_name, branch_version, expected_version, fix));
+                }
+            }
+        }
+        inconsistencies
+    }
+}
+
+/// Whether the version number is after the other one, false if it is unknown
+fn is_after(version_number: &Option<String>, other_version_number: &str) -> bool {
+    version_number.as_ref()
+        .map(|version_number| compare_to(version_number, other_version_number, Cmp::Gt).unwrap_or(false))
+        .unwrap_or(false)
+}
+
+struct Inconsistency {
+    branch_or_tag: String,
+    found: String,
+    expected: String,
+    fix: String,
+}
+
+impl Inconsistency {
+    fn new(branch_or_tag: &str, found: Option<String>, expected: String, fix: String) -> Inconsistency {
+        Inconsistency {
+            branch_or_tag: String::from(branch_or_tag),
+            found: found.unwrap_or(String::from(UNKNOWN)),
+            expected,
+            fix,
+        }
+    }
+}
+
+impl Tabled for Inconsistency {
+    const LENGTH: usize = 4;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![
+            Cow::Borrowed(&self.branch_or_tag),
+            Cow::Borrowed(&self.found),
+            Cow::Borrowed(&self.expected),
+            Cow::Borrowed(&self.fix),
+        ]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Owned(tr!(HeaderBranchOrTag)),
+            Cow::Owned(tr!(HeaderFound)),
+            Cow::Owned(tr!(HeaderExpected)),
+            Cow::Owned(tr!(HeaderFix)),
+        ]
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::execution::git_backend::GitBackend;
+use crate::execution::git_execution::GitExecution;
+
+/// Git backend running the local `git` command
+pub struct CliGitBackend {}
+
+impl GitBackend for CliGitBackend {
+    fn fetch(&self) {
+        GitExecution::fetch()
+    }
+
+    fn pull(&self) {
+        GitExecution::pull()
+    }
+
+    fn switch_branch(&self, branch_name: &str) {
+        GitExecution::switch_branch(branch_name)
+    }
+
+    fn checkout_branch(&self, branch_name: &str) {
+        GitExecution::checkout_branch(branch_name)
+    }
+
+    fn create_new_branch_from_current(&self, branch_name: &str) {
+        GitExecution::create_new_branch_from_current(branch_name)
+    }
+
+    fn merge_to_current(&self, source_branch_name: &str) {
+        GitExecution::merge_to_current(source_branch_name)
+    }
+
+    fn are_there_anything_changed(&self) -> bool {
+        GitExecution::are_there_anything_changed()
+    }
+
+    fn list_changed_files(&self) -> BTreeMap<String, String> {
+        GitExecution::list_changed_files()
+    }
+
+    fn commit(&self, message: &str) {
+        GitExecution::commit(message)
+    }
+
+    fn tag(&self, tag_name: &str) {
+        GitExecution::tag(tag_name)
+    }
+
+    fn is_branch_exists(&self, branch_name: &str, is_remote: bool) -> bool {
+        GitExecution::is_branch_exists(branch_name, is_remote)
+    }
+
+    fn list_all_branch(&self) -> Vec<String> {
+        GitExecution::list_all_branch()
+    }
+
+    fn list_remote_branch(&self) -> Vec<String> {
+        GitExecution::list_remote_branch()
+    }
+
+    fn get_current_branch_name(&self) -> Option<String> {
+        GitExecution::get_current_branch_name()
+    }
+
+    fn get_ahead_behind(&self, revision: &str, base: &str) -> Option<(usize, usize)> {
+        GitExecution::get_ahead_behind(revision, base)
+    }
+
+    fn get_commit_time(&self, revision: &str) -> Option<i64> {
+        GitExecution::get_commit_time(revision)
+    }
+
+    fn create_tracking_branch(&self, branch_name: &str) {
+        GitExecution::create_tracking_branch(branch_name)
+    }
+
+    fn read_file(&self, revision: &str, file_path: &str) -> Option<String> {
+        GitExecution::read_file(revision, file_path)
+    }
+
+    fn get_git_dir(&self) -> Option<PathBuf> {
+        GitExecution::get_git_dir()
+    }
+
+    fn get_user_name(&self) -> Option<String> {
+        GitExecution::get_user_name()
+    }
+
+    fn list_refs(&self) -> BTreeMap<String, String> {
+        GitExecution::list_refs()
+    }
+
+    fn delete_branch(&self, branch_name: &str) {
+        GitExecution::delete_branch(branch_name)
+    }
+
+    fn force_branch(&self, branch_name: &str, revision: &str) {
+        GitExecution::force_branch(branch_name, revision)
+    }
+
+    fn delete_tag(&self, tag_name: &str) {
+        GitExecution::delete_tag(tag_name)
+    }
+
+    fn force_tag(&self, tag_name: &str, revision: &str) {
+        GitExecution::force_tag(tag_name, revision)
+    }
+
+    fn list_merged_tags(&self, branch_name: &str) -> Vec<String> {
+        GitExecution::list_merged_tags(branch_name)
+    }
+
+    fn list_remote_tags(&self) -> Vec<String> {
+        GitExecution::list_remote_tags()
+    }
+
+    fn delete_remote_branch(&self, branch_name: &str) {
+        GitExecution::delete_remote_branch(branch_name)
+    }
+
+    fn push_branch(&self) {
+        GitExecution::push_branch()
+    }
+
+    fn push_tags(&self) {
+        GitExecution::push_tags()
+    }
+
+    fn push_new_branch_to_remote(&self, branch_name: &str) {
+        GitExecution::push_new_branch_to_remote(branch_name)
+    }
+}
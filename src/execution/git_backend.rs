@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use log::info;
+
+use crate::support::version::VersionUtil;
+use crate::tr;
+
+/// The git operations used by the git flows
+pub trait GitBackend {
+    /// Fetch from remote
+    fn fetch(&self);
+
+    /// Pull from remote into current branch
+    fn pull(&self);
+
+    /// Switch to branch, a local tracking branch is created if only the remote branch exists
+    fn switch_branch(&self, branch_name: &str);
+
+    /// Checkout branch
+    fn checkout_branch(&self, branch_name: &str);
+
+    /// Create a new branch from current HEAD
+    fn create_new_branch_from_current(&self, branch_name: &str);
+
+    /// Merge branch into current branch
+    fn merge_to_current(&self, source_branch_name: &str);
+
+    /// Whether there are any changes of tracked files in current branch
+    fn are_there_anything_changed(&self) -> bool;
+
+    /// The tracked files changed in the working tree or the index against HEAD,
+    /// with the object ids of their working content, empty for deleted files
+    fn list_changed_files(&self) -> BTreeMap<String, String>;
+
+    /// Stage the changes of tracked files and commit with message,
+    /// new files are only committed when they are staged already, by a hook for example
+    fn commit(&self, message: &str);
+
+    /// Create a new tag on current HEAD
+    fn tag(&self, tag_name: &str);
+
+    /// Gets the release tag of the highest version reachable from the branch, the other tags are skipped
+    fn get_last_tag_name(&self, branch_name: &str) -> Option<String> {
+        info!("{}", tr!(GitGetLastTag));
+        VersionUtil::sort_release_tags(self.list_merged_tags(branch_name))
+            .into_iter()
+            .next()
+    }
+
+    /// List the local tags reachable from the branch
+    fn list_merged_tags(&self, branch_name: &str) -> Vec<String>;
+
+    /// Whether the local or remote branch exists
+    fn is_branch_exists(&self, branch_name: &str, is_remote: bool) -> bool;
+
+    /// List all local branches
+    fn list_all_branch(&self) -> Vec<String>;
+
+    /// List all branches of remote, without the remote name prefix
+    fn list_remote_branch(&self) -> Vec<String>;
+
+    /// Gets the name of current branch, none if HEAD is detached
+    fn get_current_branch_name(&self) -> Option<String>;
+
+    /// Counts the commits of revision which are not in base and the commits of base which are not in revision
+    fn get_ahead_behind(&self, revision: &str, base: &str) -> Option<(usize, usize)>;
+
+    /// Gets the commit time of revision in seconds since epoch
+    fn get_commit_time(&self, revision: &str) -> Option<i64>;
+
+    /// Create a local branch tracking the remote branch with the same name
+    fn create_tracking_branch(&self, branch_name: &str);
+
+    /// Reads the content of file at revision
+    fn read_file(&self, revision: &str, file_path: &str) -> Option<String>;
+
+    /// Gets the absolute path of the git directory, `.git` of the working tree
+    fn get_git_dir(&self) -> Option<PathBuf>;
+
+    /// Gets the configured user, `name <email>`
+    fn get_user_name(&self) -> Option<String>;
+
+    /// Lists the full names of all branches, remote branches and tags with the object ids they point to
+    fn list_refs(&self) -> BTreeMap<String, String>;
+
+    /// Delete local branch, even if it is not merged into HEAD
+    fn delete_branch(&self, branch_name: &str);
+
+    /// Point the branch to revision, the branch is created if absent and the working tree is reset if it is checked out
+    fn force_branch(&self, branch_name: &str, revision: &str);
+
+    /// Delete local tag
+    fn delete_tag(&self, tag_name: &str);
+
+    /// Point the tag to revision, the tag is created if absent
+    fn force_tag(&self, tag_name: &str, revision: &str);
+
+    /// List all local tags
+    fn list_tags(&self) -> Vec<String> {
+        self.list_refs()
+            .into_keys()
+            .filter_map(|name| name.strip_prefix("refs/tags/").map(String::from))
+            .collect::<Vec<String>>()
+    }
+
+    /// List all tags of remote
+    fn list_remote_tags(&self) -> Vec<String>;
+
+    /// Delete branch of remote
+    fn delete_remote_branch(&self, branch_name: &str);
+
+    /// Push current branch to remote
+    fn push_branch(&self);
+
+    /// Push all tags to remote
+    fn push_tags(&self);
+
+    /// Push new branch to remote and set upstream
+    fn push_new_branch_to_remote(&self, branch_name: &str);
+}
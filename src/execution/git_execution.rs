@@ -1,12 +1,18 @@
-#[allow(dead_code)]
-#[allow(unused)]
-use std::process::abort;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
 
-use cmd_lib::{run_cmd, run_fun};
-use colour::{e_blue_ln, e_red_ln};
+use cmd_lib::{run_fun, spawn_with_output};
+use log::{debug, info, trace};
 use regex::Regex;
 use version_compare::{Cmp, compare_to};
 
+use crate::execution::git_failure::GitFailure;
+use crate::support::audit::AuditLog;
+use crate::support::failure::fail;
+use crate::support::logger::Logger;
+use crate::tr;
+
 pub const MIN_GIT_VERSION: &str = "2.23.0";
 
 pub struct GitExecution {}
@@ -15,14 +21,11 @@ impl GitExecution {
     pub fn get_git_version() -> Option<String> {
         let git_version_result = run_fun!(git --version);
         let result = match git_version_result {
-            Ok(result) => String::from(result),
-            Err(e) => {
-                e_red_ln!("Get git version error : {}", e);
-                abort();
-            }
+            Ok(result) => result,
+            Err(e) => fail(&tr!(GetGitVersionError, e)),
         };
         if !result.is_empty() {
-            e_blue_ln!("Current git version : {}", result);
+            info!("{}", tr!(CurrentGitVersion, result));
             let version_number_regex = Regex::new(r"\d*\.\d*\.\d*").unwrap();
             let current_version = version_number_regex
                 .find(result.as_str())
@@ -34,202 +37,298 @@ impl GitExecution {
         }
     }
 
-    pub fn check_git_version(current_git_version: &String) -> bool {
+    pub fn check_git_version(current_git_version: &str) -> bool {
         compare_to(current_git_version, MIN_GIT_VERSION, Cmp::Ge).unwrap_or(false)
     }
 
-    pub fn switch_branch(branch_name: &String) {
-        e_blue_ln!("[GIT]Switch to branch : {}", branch_name);
-        run_cmd!(git switch $branch_name)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
+    pub fn switch_branch(branch_name: &str) {
+        info!("{}", tr!(GitSwitchBranch, branch_name));
+        Self::run_git(&["switch", branch_name]);
     }
 
-    pub fn checkout_branch(branch_name: &String) {
-        e_blue_ln!("[GIT]Checkout branch : {}", branch_name);
-        run_cmd!(git checkout $branch_name)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
+    pub fn checkout_branch(branch_name: &str) {
+        info!("{}", tr!(GitCheckoutBranch, branch_name));
+        Self::run_git(&["checkout", branch_name]);
     }
 
     pub fn are_there_anything_changed() -> bool {
-        e_blue_ln!("[GIT]Check whether there are anything changed in current branch");
-        let git_status_result = run_fun!(git status --porcelain --untracked-files=no);
+        info!("{}", tr!(GitCheckChanges));
+        let git_status_result = Self::run_git_fun(&["status", "--porcelain", "--untracked-files=no"]);
         let result = match git_status_result {
-            Ok(result) => String::from(result),
-            Err(e) => {
-                e_red_ln!("Get git version error : {}", e);
-                abort();
-            }
+            Ok(result) => result,
+            Err(e) => fail(&tr!(GetGitStatusError, e)),
         };
         !result.is_empty()
     }
 
-    pub fn create_new_branch_from_current(branch_name: &String) {
-        e_blue_ln!("[GIT]Create a new branch : {}", branch_name);
-        run_cmd!(git branch $branch_name)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
+    pub fn create_new_branch_from_current(branch_name: &str) {
+        info!("{}", tr!(GitCreateBranch, branch_name));
+        Self::run_git(&["branch", branch_name]);
     }
 
-    pub fn merge_to_current(source_branch_name: &String) {
-        e_blue_ln!(
-            "[GIT]Merge branch to current,Branch name : {}",
-            source_branch_name
-        );
-        run_cmd!(git merge $source_branch_name)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
+    pub fn merge_to_current(source_branch_name: &str) {
+        info!("{}", tr!(GitMergeToCurrent, source_branch_name));
+        Self::run_git(&["merge", source_branch_name]);
     }
 
     pub fn fetch() {
-        e_blue_ln!("[GIT]Fetch from remote");
-        run_cmd!(git fetch)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
+        info!("{}", tr!(GitFetch));
+        Self::run_git(&["fetch"]);
     }
 
     pub fn pull() {
-        e_blue_ln!("[GIT]Pull from remote");
-        run_cmd!(git pull)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
+        info!("{}", tr!(GitPull));
+        Self::run_git(&["pull"]);
+    }
+
+    pub fn delete_branch(branch_name: &str) {
+        info!("{}", tr!(GitDeleteBranch, branch_name));
+        Self::run_git(&["branch", "-D", branch_name]);
+    }
+
+    pub fn delete_remote_branch(branch_name: &str) {
+        info!("{}", tr!(GitDeleteRemoteBranch, branch_name));
+        Self::run_git(&["push", "origin", "--delete", branch_name]);
+    }
+
+    pub fn force_branch(branch_name: &str, revision: &str) {
+        info!("{}", tr!(GitForceBranch, branch_name, revision));
+        if Self::get_current_branch_name().as_deref() == Some(branch_name) {
+            Self::run_git(&["reset", "--hard", revision]);
+        } else {
+            Self::run_git(&["branch", "--force", branch_name, revision]);
+        }
+    }
+
+    pub fn delete_tag(tag_name: &str) {
+        info!("{}", tr!(GitDeleteTag, tag_name));
+        Self::run_git(&["tag", "--delete", tag_name]);
+    }
+
+    pub fn force_tag(tag_name: &str, revision: &str) {
+        info!("{}", tr!(GitForceTag, tag_name, revision));
+        Self::run_git(&["tag", "--force", tag_name, revision]);
+    }
+
+    pub fn list_merged_tags(branch_name: &str) -> Vec<String> {
+        let result = Self::run_git_fun(&["tag", "--merged", branch_name]);
+        result.unwrap_or_default()
+            .lines()
+            .map(String::from)
+            .collect::<Vec<String>>()
+    }
+
+    pub fn list_remote_tags() -> Vec<String> {
+        let arguments = ["ls-remote", "--tags", "origin"];
+        let (result, stderr) = Self::run_git_captured(&arguments);
+        match &result {
+            Ok(output) => output.lines()
+                .filter_map(|line| line.split_once("refs/tags/"))
+                .map(|(_, tag_name)| tag_name)
+                .filter(|tag_name| !tag_name.ends_with("^{}"))
+                .map(String::from)
+                .collect::<Vec<String>>(),
+            Err(_) => Self::fail_with_stderr(&arguments, &result, &stderr),
+        }
     }
 
     pub fn push_branch() {
-        e_blue_ln!("[GIT]Push to remote");
-        run_cmd!(git push)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
+        info!("{}", tr!(GitPush));
+        Self::run_git(&["push"]);
     }
 
     pub fn push_tags() {
-        e_blue_ln!("[GIT]Push tags to remote");
-        run_cmd!(git push --tags)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
-    }
-
-    pub fn is_branch_exists(branch_name: &String, is_remote: bool) -> bool {
-        let is_remote_display = if is_remote { "remote" } else { "local" };
-        e_blue_ln!(
-            "[GIT]Check if {} branch exists : {}",
-            is_remote_display, branch_name
-        );
+        info!("{}", tr!(GitPushTags));
+        Self::run_git(&["push", "--tags"]);
+    }
+
+    pub fn is_branch_exists(branch_name: &str, is_remote: bool) -> bool {
+        if is_remote {
+            info!("{}", tr!(GitCheckRemoteBranchExists, branch_name));
+        } else {
+            info!("{}", tr!(GitCheckLocalBranchExists, branch_name));
+        }
         let branch_to_verify: String = if is_remote {
             let mut origin = String::from("origin/");
-            origin.push_str(branch_name.as_str());
+            origin.push_str(branch_name);
             origin
         } else {
             branch_name.to_string()
         };
-        let verify_result = run_fun!(git rev-parse --verify $branch_to_verify);
-        let result = match verify_result {
-            Ok(result) => Some(String::from(result)),
-            Err(_) => {
-                None
-            }
-        };
+        let verify_result = Self::run_git_fun(&["rev-parse", "--verify", &branch_to_verify]);
+        let result = verify_result.ok();
         if result.is_none() {
             return false;
         }
         !result.unwrap().starts_with("fatal:")
     }
 
-    pub fn push_new_branch_to_remote(branch_name: &String) {
-        print!("[GIT]Push new branch to remote : {}", branch_name);
-        run_cmd!(git push --set-upstream origin $branch_name)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
-    }
-
-    pub fn commit(message: String) {
-        e_blue_ln!("[GIT]Commit with message");
-        run_cmd!(git add ./)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
-        run_cmd!(git commit -m $message)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
-    }
-
-    pub fn tag(tag_name: &String) {
-        e_blue_ln!("[GIT]Create new tag : {}", tag_name);
-        run_cmd!(git tag $tag_name)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
-    }
-
-    pub fn get_last_tag_name() -> Option<String> {
-        e_blue_ln!("[GIT]Get latest tag name");
-        let git_tag_result = run_fun!(git rev-list --tags --max-count=1);
-        let git_tag = match git_tag_result {
-            Ok(output) => Some(String::from(output)),
-            Err(_err) => None,
-        };
-        if git_tag.is_none() {
-            return None;
-        }
-        let git_tag_value = git_tag.unwrap();
-        let actual_tag = run_fun!(git describe --tags $git_tag_value);
-        match actual_tag {
-            Ok(output) => Some(String::from(output)),
-            Err(_err) => None,
+    pub fn push_new_branch_to_remote(branch_name: &str) {
+        info!("{}", tr!(GitPushNewBranch, branch_name));
+        Self::run_git(&["push", "--set-upstream", "origin", branch_name]);
+    }
+
+    pub fn list_changed_files() -> BTreeMap<String, String> {
+        let changed_paths = Self::run_git_fun(&["diff", "HEAD", "--name-only"]).unwrap_or_default();
+        let (existing_paths, deleted_paths): (Vec<&str>, Vec<&str>) = changed_paths.lines()
+            .partition(|path| Path::new(path).is_file());
+        let mut changed_files = deleted_paths.into_iter()
+            .map(|path| (String::from(path), String::new()))
+            .collect::<BTreeMap<String, String>>();
+        if !existing_paths.is_empty() {
+            let mut arguments = vec!["hash-object", "--"];
+            arguments.extend(existing_paths.iter());
+            let object_ids = Self::run_git_fun(&arguments).unwrap_or_default();
+            changed_files.extend(existing_paths.iter().map(|path| String::from(*path)).zip(object_ids.lines().map(String::from)));
         }
+        changed_files
+    }
+
+    /// Stages the changes of tracked files and commits, untracked files like build output are left out
+    pub fn commit(message: &str) {
+        info!("{}", tr!(GitCommit));
+        Self::run_git(&["add", "--update"]);
+        Self::run_git(&["commit", "-m", message]);
+    }
+
+    pub fn tag(tag_name: &str) {
+        info!("{}", tr!(GitCreateTag, tag_name));
+        Self::run_git(&["tag", tag_name]);
     }
 
     pub fn list_all_branch() -> Vec<String> {
-        let result = run_fun!(git branch | cut -c 3-);
-        let option_result = match result {
-            Ok(value) => Some(String::from(value)),
-            Err(_e) => None
-        };
+        let result = Self::run_git_fun(&["branch", "--format=%(refname:short)"]);
+        let option_result = result.ok();
         let option_branch_vec = option_result
             .map(|value| {
-                value.split("\n")
+                value.lines()
+                    // skip the `(HEAD detached at xxx)` line
+                    .filter(|line| !line.starts_with('('))
                     .map(|s| s.to_string())
                     .collect::<Vec<String>>()
             });
-        if option_branch_vec.is_none() {
-            e_red_ln!("未获取到任何本地分支");
-            abort();
+        match option_branch_vec {
+            Some(branch_vec) => branch_vec,
+            None => fail(&tr!(NoLocalBranch)),
         }
-        option_branch_vec.unwrap()
+    }
+
+    pub fn list_remote_branch() -> Vec<String> {
+        let result = Self::run_git_fun(&["branch", "--remotes", "--format=%(refname:short)"]);
+        result.unwrap_or_default()
+            .lines()
+            .filter_map(|line| line.strip_prefix("origin/"))
+            .filter(|branch_name| *branch_name != "HEAD")
+            .map(String::from)
+            .collect::<Vec<String>>()
+    }
+
+    pub fn get_current_branch_name() -> Option<String> {
+        let result = Self::run_git_fun(&["branch", "--show-current"]).ok()?;
+        if result.is_empty() {
+            return None;
+        }
+        Some(result)
+    }
+
+    pub fn get_ahead_behind(revision: &str, base: &str) -> Option<(usize, usize)> {
+        let range = format!("{}...{}", base, revision);
+        let result = Self::run_git_fun(&["rev-list", "--left-right", "--count", &range]).ok()?;
+        let counts = result.split_whitespace()
+            .map(|count| count.parse::<usize>())
+            .collect::<Result<Vec<usize>, _>>()
+            .ok()?;
+        if counts.len() != 2 {
+            return None;
+        }
+        Some((counts[1], counts[0]))
+    }
+
+    pub fn get_commit_time(revision: &str) -> Option<i64> {
+        let result = Self::run_git_fun(&["log", "-1", "--format=%ct", revision]).ok()?;
+        result.trim().parse::<i64>().ok()
+    }
+
+    pub fn create_tracking_branch(branch_name: &str) {
+        info!("{}", tr!(GitCreateTrackingBranch, branch_name));
+        let remote_branch_name = format!("origin/{}", branch_name);
+        Self::run_git(&["branch", "--track", branch_name, &remote_branch_name]);
+    }
+
+    pub fn read_file(revision: &str, file_path: &str) -> Option<String> {
+        let object = format!("{}:{}", revision, file_path);
+        let result = Logger::timed(&Self::command_line_of(&["show", &object]), || run_fun!(git show $object 2>/dev/null));
+        Self::record(&["show", &object], &result);
+        result.ok()
+    }
+
+    pub fn get_git_dir() -> Option<PathBuf> {
+        Self::run_git_fun(&["rev-parse", "--absolute-git-dir"]).ok().map(PathBuf::from)
+    }
+
+    pub fn get_user_name() -> Option<String> {
+        let user_name = Self::run_git_fun(&["config", "user.name"]).ok()?;
+        match Self::run_git_fun(&["config", "user.email"]) {
+            Ok(email) => Some(format!("{} <{}>", user_name, email)),
+            Err(_) => Some(user_name),
+        }
+    }
+
+    pub fn list_refs() -> BTreeMap<String, String> {
+        Self::run_git_fun(&["for-each-ref", "--format=%(refname) %(objectname)"])
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .map(|(name, id)| (String::from(name), String::from(id)))
+            .collect::<BTreeMap<String, String>>()
+    }
+
+    /// Runs git and records it into the audit log, aborts with what git printed and its explanation if git fails
+    fn run_git(arguments: &[&str]) {
+        let (result, stderr) = Self::run_git_captured(arguments);
+        match &result {
+            Ok(output) => output.lines().for_each(|line| debug!("{}", line)),
+            Err(_) => Self::fail_with_stderr(arguments, &result, &stderr),
+        }
+    }
+
+    /// Aborts with the failed command and what git printed, explained by [GitFailure] when it is recognized
+    fn fail_with_stderr(arguments: &[&str], result: &io::Result<String>, stderr: &str) -> ! {
+        let output = match result {
+            // git printed nothing when it could not be started at all
+            Err(err) if stderr.trim().is_empty() => err.to_string(),
+            _ => String::from(stderr),
+        };
+        fail(&GitFailure::message_of(arguments, AuditLog::exit_code_of(result), &output))
+    }
+
+    /// Runs git and records it into the audit log, returns the output
+    fn run_git_fun(arguments: &[&str]) -> io::Result<String> {
+        let (result, _) = Self::run_git_captured(arguments);
+        if let Ok(output) = &result {
+            trace!("{}", output);
+        }
+        result
+    }
+
+    /// Runs git with its stderr captured, the stderr is logged and kept to explain the failure
+    fn run_git_captured(arguments: &[&str]) -> (io::Result<String>, String) {
+        let mut stderr = String::new();
+        let result = Logger::timed(&Self::command_line_of(arguments), || {
+            let (result, output, error_output) = spawn_with_output!(git $[arguments])?.wait_with_all();
+            stderr = error_output;
+            result.map(|_| output)
+        });
+        stderr.lines().for_each(|line| debug!("{}", line));
+        Self::record(arguments, &result);
+        (result, stderr)
+    }
+
+    fn record<T>(arguments: &[&str], result: &io::Result<T>) {
+        AuditLog::record_command(Self::command_line_of(arguments), AuditLog::exit_code_of(result));
+    }
+
+    fn command_line_of(arguments: &[&str]) -> String {
+        "git ".to_owned() + arguments.join(" ").as_str()
     }
 }
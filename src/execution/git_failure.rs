@@ -0,0 +1,154 @@
+use git2::ErrorCode;
+
+use crate::tr;
+
+/// The common failures of git, recognized from what git prints to stderr
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum GitFailure {
+    //Uncommitted or untracked changes would be overwritten
+    DirtyWorkingTree,
+
+    //The remote branch has commits which the local branch does not have
+    NonFastForward,
+
+    //The remote refused the credentials
+    AuthenticationFailed,
+
+    //The branch, tag or revision does not exist
+    MissingRef,
+
+    //The merge stopped at conflicts
+    MergeConflict,
+}
+
+/// Parts of the git messages of each failure, in lower case, checked in order
+const FAILURE_MESSAGES: [(GitFailure, &[&str]); 5] = [
+    (GitFailure::MergeConflict, &["conflict (", "automatic merge failed", "fix conflicts and then commit", "you have not concluded your merge"]),
+    (GitFailure::DirtyWorkingTree, &["would be overwritten by", "commit your changes or stash them", "you have unstaged changes", "your index contains uncommitted changes"]),
+    (GitFailure::NonFastForward, &["non-fast-forward", "updates were rejected", "fetch first", "non-fastforward", "not possible to fast-forward", "divergent branches"]),
+    (GitFailure::AuthenticationFailed, &["authentication failed", "permission denied", "could not read username", "could not read password", "the requested url returned error: 403"]),
+    (GitFailure::MissingRef, &["invalid reference", "did not match any", "unknown revision", "couldn't find remote ref", "not a valid object name", "not something we can merge"]),
+];
+
+impl GitFailure {
+    pub fn classify(stderr: &str) -> Option<GitFailure> {
+        let stderr = stderr.to_lowercase();
+        FAILURE_MESSAGES.iter()
+            .find(|(_, messages)| messages.iter().any(|message| stderr.contains(message)))
+            .map(|(failure, _)| failure.clone())
+    }
+
+    /// The failure of a libgit2 error by its code, or by its message like the stderr of git
+    pub fn of_libgit2_error(err: &git2::Error) -> Option<GitFailure> {
+        match err.code() {
+            ErrorCode::Conflict | ErrorCode::Uncommitted => Some(GitFailure::DirtyWorkingTree),
+            ErrorCode::NotFastForward => Some(GitFailure::NonFastForward),
+            ErrorCode::Auth | ErrorCode::Certificate => Some(GitFailure::AuthenticationFailed),
+            ErrorCode::NotFound | ErrorCode::InvalidSpec => Some(GitFailure::MissingRef),
+            ErrorCode::MergeConflict | ErrorCode::Unmerged => Some(GitFailure::MergeConflict),
+            _ => Self::classify(err.message()),
+        }
+    }
+
+    pub fn explanation(&self) -> String {
+        match self {
+            GitFailure::DirtyWorkingTree => tr!(GitDirtyWorkingTree),
+            GitFailure::NonFastForward => tr!(GitNonFastForward),
+            GitFailure::AuthenticationFailed => tr!(GitAuthenticationFailed),
+            GitFailure::MissingRef => tr!(GitMissingRef),
+            GitFailure::MergeConflict => tr!(GitMergeConflict),
+        }
+    }
+
+    /// The command to run before running the failed git command again
+    pub fn fix_command(&self, arguments: &[&str]) -> String {
+        match self {
+            GitFailure::DirtyWorkingTree => String::from("git stash push --include-untracked"),
+            GitFailure::NonFastForward if arguments.first() == Some(&"push") => {
+                format!("git pull --rebase && git {}", arguments.join(" "))
+            }
+            GitFailure::NonFastForward => String::from("git pull --rebase"),
+            GitFailure::AuthenticationFailed => String::from("git ls-remote origin"),
+            GitFailure::MissingRef => String::from("git fetch --prune origin && git branch --all"),
+            GitFailure::MergeConflict => String::from("git merge --abort"),
+        }
+    }
+
+    /// The message followed by the explanation and the fix
+    pub fn explain(&self, message: &str, arguments: &[&str]) -> String {
+        message.to_owned() + "\n" + self.explanation().as_str() + "\n" + tr!(GitSuggestedFix, self.fix_command(arguments)).as_str()
+    }
+
+    /// The failed command with what git printed, explained with the fix when the failure is recognized
+    pub fn message_of(arguments: &[&str], exit_code: Option<i32>, stderr: &str) -> String {
+        let command_line = "git ".to_owned() + arguments.join(" ").as_str();
+        let exit_code = exit_code.map(|exit_code| exit_code.to_string()).unwrap_or(String::from("-"));
+        let mut message = tr!(GitCommandFailed, command_line, exit_code);
+        if !stderr.trim().is_empty() {
+            message = message + "\n" + stderr.trim_end();
+        }
+        match Self::classify(stderr) {
+            Some(failure) => failure.explain(&message, arguments),
+            None => message,
+        }
+    }
+
+    /// The message of the libgit2 error, explained with the fix when the failure is recognized
+    pub fn message_of_libgit2_error(err: &git2::Error) -> String {
+        match Self::of_libgit2_error(err) {
+            Some(failure) => failure.explain(err.message(), &[]),
+            None => String::from(err.message()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use git2::{ErrorClass, ErrorCode};
+
+    use crate::execution::git_failure::GitFailure;
+
+    #[test]
+    fn classify_stderr_of_git() {
+        let dirty = "error: Your local changes to the following files would be overwritten by checkout:\n\tpackage.json\nPlease commit your changes or stash them before you switch branches.\nAborting";
+        assert_eq!(Some(GitFailure::DirtyWorkingTree), GitFailure::classify(dirty));
+        let rejected = " ! [rejected]        master -> master (non-fast-forward)\nerror: failed to push some refs to 'origin'";
+        assert_eq!(Some(GitFailure::NonFastForward), GitFailure::classify(rejected));
+        let fetch_first = " ! [rejected]        develop -> develop (fetch first)";
+        assert_eq!(Some(GitFailure::NonFastForward), GitFailure::classify(fetch_first));
+        let divergent = "hint: You have divergent branches and need to specify how to reconcile them.\nfatal: Need to specify how to reconcile divergent branches.";
+        assert_eq!(Some(GitFailure::NonFastForward), GitFailure::classify(divergent));
+        let authentication = "remote: Invalid username or password.\nfatal: Authentication failed for 'https://example.com/app.git/'";
+        assert_eq!(Some(GitFailure::AuthenticationFailed), GitFailure::classify(authentication));
+        let public_key = "git@example.com: Permission denied (publickey).\nfatal: Could not read from remote repository.";
+        assert_eq!(Some(GitFailure::AuthenticationFailed), GitFailure::classify(public_key));
+        assert_eq!(Some(GitFailure::MissingRef), GitFailure::classify("fatal: invalid reference: test/1.3.0"));
+        assert_eq!(Some(GitFailure::MissingRef), GitFailure::classify("merge: feature/9.9.9 - not something we can merge"));
+        let conflict = "Auto-merging package.json\nCONFLICT (content): Merge conflict in package.json\nAutomatic merge failed; fix conflicts and then commit the result.";
+        assert_eq!(Some(GitFailure::MergeConflict), GitFailure::classify(conflict));
+        assert_eq!(None, GitFailure::classify("fatal: unable to access 'https://example.com/': Could not resolve host"));
+    }
+
+    #[test]
+    fn classify_libgit2_errors() {
+        let checkout_conflict = git2::Error::new(ErrorCode::Conflict, ErrorClass::Checkout, "1 conflict prevents checkout");
+        assert_eq!(Some(GitFailure::DirtyWorkingTree), GitFailure::of_libgit2_error(&checkout_conflict));
+        let not_found = git2::Error::new(ErrorCode::NotFound, ErrorClass::Reference, "reference 'refs/heads/test/1.3.0' not found");
+        assert_eq!(Some(GitFailure::MissingRef), GitFailure::of_libgit2_error(&not_found));
+        let rejected = git2::Error::from_str("Push of refs/heads/master rejected : cannot push non-fastforward reference");
+        assert_eq!(Some(GitFailure::NonFastForward), GitFailure::of_libgit2_error(&rejected));
+        let auth = git2::Error::new(ErrorCode::Auth, ErrorClass::Callback, "认证失败");
+        let message = GitFailure::message_of_libgit2_error(&auth);
+        assert!(message.starts_with("认证失败\n"), "{}", message);
+        assert!(message.ends_with("git ls-remote origin"), "{}", message);
+        assert_eq!(None, GitFailure::of_libgit2_error(&git2::Error::from_str("failed to resolve address")));
+    }
+
+    #[test]
+    fn fix_command_of_push() {
+        assert_eq!("git pull --rebase && git push --tags", GitFailure::NonFastForward.fix_command(&["push", "--tags"]));
+        assert_eq!("git pull --rebase", GitFailure::NonFastForward.fix_command(&["pull"]));
+    }
+}
@@ -0,0 +1,82 @@
+use std::io;
+use std::process::{Command, Stdio};
+
+use log::info;
+
+use crate::support::audit::AuditLog;
+use crate::support::enums::{FlowType, HookPoint, ReleaseType};
+use crate::support::failure::fail;
+use crate::tr;
+
+/// What the flow has done so far, passed to the hooks as `GITFLOW_*` environment variables
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct HookContext {
+    pub flow_type: FlowType,
+    pub release_type: Option<ReleaseType>,
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+    pub branch: Option<String>,
+    pub tag: Option<String>,
+}
+
+impl HookContext {
+    pub fn new(flow_type: FlowType, release_type: Option<ReleaseType>) -> HookContext {
+        HookContext {
+            flow_type,
+            release_type,
+            old_version: None,
+            new_version: None,
+            branch: None,
+            tag: None,
+        }
+    }
+
+    /// The environment variables, empty values for what the flow does not know yet
+    pub fn environment_variables(&self, hook_point: &HookPoint) -> Vec<(&'static str, String)> {
+        vec![
+            ("GITFLOW_HOOK", hook_point.to_string()),
+            ("GITFLOW_FLOW_TYPE", self.flow_type.to_string()),
+            ("GITFLOW_RELEASE_TYPE", self.release_type.as_ref().map(ReleaseType::to_string).unwrap_or_default()),
+            ("GITFLOW_OLD_VERSION", self.old_version.clone().unwrap_or_default()),
+            ("GITFLOW_NEW_VERSION", self.new_version.clone().unwrap_or_default()),
+            ("GITFLOW_BRANCH", self.branch.clone().unwrap_or_default()),
+            ("GITFLOW_TAG", self.tag.clone().unwrap_or_default()),
+        ]
+    }
+}
+
+pub struct HookExecution {}
+
+impl HookExecution {
+    /// Runs the commands of the hook by the shell in order, aborts the flow at the first failure
+    pub fn run(hook_point: &HookPoint, commands: &[String], context: &HookContext) {
+        if let Err(message) = Self::try_run(hook_point, commands, context) {
+            fail(&message);
+        }
+    }
+
+    /// Runs the commands of the hook by the shell in order, stops at the first failure
+    pub fn try_run(hook_point: &HookPoint, commands: &[String], context: &HookContext) -> Result<(), String> {
+        for command in commands {
+            info!("{}", tr!(HookRun, hook_point, command));
+            // the output of hooks goes to stderr with the other logs of the flow
+            let status = Command::new("sh")
+                .arg("-c")
+                .arg(command)
+                .envs(context.environment_variables(hook_point))
+                .stdout(Stdio::from(io::stderr()))
+                .status();
+            AuditLog::record_command(
+                format!("{} hook : {}", hook_point, command),
+                status.as_ref().ok().and_then(|status| status.code()),
+            );
+            match status {
+                Ok(status) if status.success() => {}
+                Ok(status) => return Err(tr!(HookFailed, hook_point, command, status)),
+                Err(err) => return Err(tr!(HookFailed, hook_point, command, err)),
+            }
+        }
+        Ok(())
+    }
+}
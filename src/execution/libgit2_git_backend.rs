@@ -0,0 +1,449 @@
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use log::info;
+use git2::build::CheckoutBuilder;
+use git2::{
+    AnnotatedCommit, AutotagOption, BranchType, Cred, CredentialType, Direction,
+    ErrorClass, ErrorCode, FetchOptions, ObjectType, Oid, PushOptions, RemoteCallbacks,
+    Repository, ResetType, StatusOptions,
+};
+
+use crate::execution::git_backend::GitBackend;
+use crate::execution::git_failure::GitFailure;
+use crate::support::audit::AuditLog;
+use crate::support::failure::fail;
+use crate::tr;
+
+const REMOTE_NAME: &str = "origin";
+
+/// Git backend running git operations in process through libgit2
+pub struct Libgit2GitBackend {
+    repository: Repository,
+}
+
+impl Libgit2GitBackend {
+    pub fn open() -> Libgit2GitBackend {
+        let repository = Self::or_abort(Repository::discover("./"));
+        Libgit2GitBackend { repository }
+    }
+
+    fn or_abort<T>(result: Result<T, git2::Error>) -> T {
+        match result {
+            Ok(value) => value,
+            Err(err) => fail(&GitFailure::message_of_libgit2_error(&err)),
+        }
+    }
+
+    /// Records the git operation done in process into the audit log
+    fn record(operation: String) {
+        AuditLog::record_command("libgit2 ".to_owned() + operation.as_str(), Some(0));
+    }
+
+    fn remote_callbacks(&self) -> RemoteCallbacks<'static> {
+        let config = self.repository.config().ok();
+        let attempts = Cell::new(0);
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.credentials(move |url, username_from_url, allowed_types| {
+            attempts.set(attempts.get() + 1);
+            if attempts.get() > 3 {
+                return Err(git2::Error::new(ErrorCode::Auth, ErrorClass::Callback, tr!(AuthenticationFailed)));
+            }
+            if allowed_types.contains(CredentialType::SSH_KEY) {
+                return Cred::ssh_key_from_agent(username_from_url.unwrap_or("git"));
+            }
+            if allowed_types.contains(CredentialType::USER_PASS_PLAINTEXT) {
+                if let Some(config) = &config {
+                    return Cred::credential_helper(config, url, username_from_url);
+                }
+            }
+            Cred::default()
+        });
+        callbacks.push_update_reference(|reference_name, status| match status {
+            Some(message) => Err(git2::Error::from_str(
+                tr!(PushRejected, reference_name, message).as_str(),
+            )),
+            None => Ok(()),
+        });
+        callbacks
+    }
+
+    fn push_refspecs(&self, refspecs: &[String]) {
+        let mut remote = Self::or_abort(self.repository.find_remote(REMOTE_NAME));
+        let mut push_options = PushOptions::new();
+        push_options.remote_callbacks(self.remote_callbacks());
+        Self::or_abort(remote.push(refspecs, Some(&mut push_options)));
+    }
+
+    fn current_branch_name(&self) -> String {
+        let head = Self::or_abort(self.repository.head());
+        match head.shorthand() {
+            Ok(name) if head.is_branch() => String::from(name),
+            _ => fail(&tr!(HeadNotBranch)),
+        }
+    }
+
+    fn checkout_local_branch(&self, branch_name: &str) {
+        let branch = Self::or_abort(self.repository.find_branch(branch_name, BranchType::Local));
+        let reference_name = match branch.get().name() {
+            Ok(name) => String::from(name),
+            Err(_) => fail(&tr!(InvalidBranchName, branch_name)),
+        };
+        let tree = Self::or_abort(branch.get().peel(ObjectType::Tree));
+        Self::or_abort(
+            self.repository
+                .checkout_tree(&tree, Some(CheckoutBuilder::new().safe())),
+        );
+        Self::or_abort(self.repository.set_head(reference_name.as_str()));
+    }
+
+    fn merge_annotated_commit(&self, annotated_commit: &AnnotatedCommit, message: &str) {
+        let (analysis, _) =
+            Self::or_abort(self.repository.merge_analysis(&[annotated_commit]));
+        if analysis.is_up_to_date() {
+            return;
+        }
+        if analysis.is_fast_forward() {
+            // the working tree moves to the target before HEAD, so the safe checkout compares it with the old HEAD
+            let target = Self::or_abort(self.repository.find_object(annotated_commit.id(), Some(ObjectType::Commit)));
+            Self::or_abort(
+                self.repository
+                    .checkout_tree(&target, Some(CheckoutBuilder::new().safe())),
+            );
+            let mut head = Self::or_abort(self.repository.head());
+            Self::or_abort(head.set_target(annotated_commit.id(), message));
+            return;
+        }
+        Self::or_abort(self.repository.merge(&[annotated_commit], None, None));
+        let mut index = Self::or_abort(self.repository.index());
+        if index.has_conflicts() {
+            fail(&GitFailure::MergeConflict.explain(&tr!(MergeConflict, message), &[]));
+        }
+        let tree_id = Self::or_abort(index.write_tree());
+        let tree = Self::or_abort(self.repository.find_tree(tree_id));
+        let signature = Self::or_abort(self.repository.signature());
+        let head_commit = Self::or_abort(self.repository.head().and_then(|head| head.peel_to_commit()));
+        let source_commit = Self::or_abort(self.repository.find_commit(annotated_commit.id()));
+        Self::or_abort(self.repository.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &[&head_commit, &source_commit],
+        ));
+        Self::or_abort(self.repository.cleanup_state());
+    }
+}
+
+impl GitBackend for Libgit2GitBackend {
+    fn fetch(&self) {
+        info!("{}", tr!(GitFetch));
+        let mut remote = Self::or_abort(self.repository.find_remote(REMOTE_NAME));
+        let mut fetch_options = FetchOptions::new();
+        fetch_options
+            .remote_callbacks(self.remote_callbacks())
+            .download_tags(AutotagOption::All);
+        Self::or_abort(remote.fetch(&[] as &[&str], Some(&mut fetch_options), None));
+        Self::record(String::from("fetch origin"));
+    }
+
+    fn pull(&self) {
+        info!("{}", tr!(GitPull));
+        self.fetch();
+        let branch_name = self.current_branch_name();
+        let branch = Self::or_abort(self.repository.find_branch(&branch_name, BranchType::Local));
+        let upstream = match branch.upstream() {
+            Ok(upstream) => upstream,
+            Err(_) => fail(&tr!(NoTrackingInformation, branch_name)),
+        };
+        let annotated_commit =
+            Self::or_abort(self.repository.reference_to_annotated_commit(upstream.get()));
+        let message = format!("Merge branch '{}' of {}", branch_name, REMOTE_NAME);
+        self.merge_annotated_commit(&annotated_commit, &message);
+        Self::record(String::from("pull"));
+    }
+
+    fn switch_branch(&self, branch_name: &str) {
+        info!("{}", tr!(GitSwitchBranch, branch_name));
+        if !self.is_branch_exists(branch_name, false) {
+            self.create_tracking_branch(branch_name);
+        }
+        self.checkout_local_branch(branch_name);
+        Self::record("switch ".to_owned() + branch_name);
+    }
+
+    fn checkout_branch(&self, branch_name: &str) {
+        info!("{}", tr!(GitCheckoutBranch, branch_name));
+        self.checkout_local_branch(branch_name);
+        Self::record("checkout ".to_owned() + branch_name);
+    }
+
+    fn create_new_branch_from_current(&self, branch_name: &str) {
+        info!("{}", tr!(GitCreateBranch, branch_name));
+        let head_commit = Self::or_abort(self.repository.head().and_then(|head| head.peel_to_commit()));
+        Self::or_abort(self.repository.branch(branch_name, &head_commit, false));
+        Self::record("branch ".to_owned() + branch_name);
+    }
+
+    fn merge_to_current(&self, source_branch_name: &str) {
+        info!("{}", tr!(GitMergeToCurrent, source_branch_name));
+        let source = Self::or_abort(self.repository.revparse_single(source_branch_name));
+        let annotated_commit = Self::or_abort(self.repository.find_annotated_commit(source.id()));
+        let message = format!("Merge branch '{}'", source_branch_name);
+        self.merge_annotated_commit(&annotated_commit, &message);
+        Self::record("merge ".to_owned() + source_branch_name);
+    }
+
+    fn are_there_anything_changed(&self) -> bool {
+        info!("{}", tr!(GitCheckChanges));
+        let mut status_options = StatusOptions::new();
+        status_options.include_untracked(false).include_ignored(false);
+        let statuses = Self::or_abort(self.repository.statuses(Some(&mut status_options)));
+        !statuses.is_empty()
+    }
+
+    fn list_changed_files(&self) -> BTreeMap<String, String> {
+        let mut status_options = StatusOptions::new();
+        status_options.include_untracked(false).include_ignored(false);
+        let statuses = Self::or_abort(self.repository.statuses(Some(&mut status_options)));
+        let workdir = self.repository.workdir().map(Path::to_path_buf).unwrap_or_default();
+        statuses.iter()
+            .filter_map(|entry| {
+                let path = String::from(entry.path().ok()?);
+                let object_id = Oid::hash_file(ObjectType::Blob, workdir.join(&path))
+                    .map(|id| id.to_string())
+                    .unwrap_or_default();
+                Some((path, object_id))
+            })
+            .collect::<BTreeMap<String, String>>()
+    }
+
+    fn commit(&self, message: &str) {
+        info!("{}", tr!(GitCommit));
+        let mut index = Self::or_abort(self.repository.index());
+        Self::or_abort(index.update_all(["."], None));
+        Self::or_abort(index.write());
+        let tree_id = Self::or_abort(index.write_tree());
+        let tree = Self::or_abort(self.repository.find_tree(tree_id));
+        let signature = Self::or_abort(self.repository.signature());
+        let head_commit = Self::or_abort(self.repository.head().and_then(|head| head.peel_to_commit()));
+        Self::or_abort(self.repository.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &[&head_commit],
+        ));
+        Self::record("commit -m ".to_owned() + message);
+    }
+
+    fn tag(&self, tag_name: &str) {
+        info!("{}", tr!(GitCreateTag, tag_name));
+        let head = Self::or_abort(self.repository.head().and_then(|head| head.peel(ObjectType::Commit)));
+        Self::or_abort(self.repository.tag_lightweight(tag_name, &head, false));
+        Self::record("tag ".to_owned() + tag_name);
+    }
+
+    fn is_branch_exists(&self, branch_name: &str, is_remote: bool) -> bool {
+        if is_remote {
+            info!("{}", tr!(GitCheckRemoteBranchExists, branch_name));
+        } else {
+            info!("{}", tr!(GitCheckLocalBranchExists, branch_name));
+        }
+        if is_remote {
+            let remote_branch_name = format!("{}/{}", REMOTE_NAME, branch_name);
+            self.repository.find_branch(&remote_branch_name, BranchType::Remote).is_ok()
+        } else {
+            self.repository.find_branch(branch_name, BranchType::Local).is_ok()
+        }
+    }
+
+    fn list_all_branch(&self) -> Vec<String> {
+        let branches = Self::or_abort(self.repository.branches(Some(BranchType::Local)));
+        branches
+            .flatten()
+            .filter_map(|(branch, _)| branch.name().ok().flatten().map(String::from))
+            .collect::<Vec<String>>()
+    }
+
+    fn list_remote_branch(&self) -> Vec<String> {
+        let branches = Self::or_abort(self.repository.branches(Some(BranchType::Remote)));
+        let prefix = format!("{}/", REMOTE_NAME);
+        branches
+            .flatten()
+            .filter_map(|(branch, _)| branch.name().ok().flatten().map(String::from))
+            .filter_map(|branch_name| branch_name.strip_prefix(prefix.as_str()).map(String::from))
+            .filter(|branch_name| branch_name != "HEAD")
+            .collect::<Vec<String>>()
+    }
+
+    fn get_current_branch_name(&self) -> Option<String> {
+        let head = self.repository.head().ok()?;
+        if !head.is_branch() {
+            return None;
+        }
+        head.shorthand().ok().map(String::from)
+    }
+
+    fn get_ahead_behind(&self, revision: &str, base: &str) -> Option<(usize, usize)> {
+        let revision_commit = self.repository.revparse_single(revision).and_then(|object| object.peel_to_commit()).ok()?;
+        let base_commit = self.repository.revparse_single(base).and_then(|object| object.peel_to_commit()).ok()?;
+        self.repository.graph_ahead_behind(revision_commit.id(), base_commit.id()).ok()
+    }
+
+    fn get_commit_time(&self, revision: &str) -> Option<i64> {
+        let commit = self.repository.revparse_single(revision).and_then(|object| object.peel_to_commit()).ok()?;
+        Some(commit.time().seconds())
+    }
+
+    fn create_tracking_branch(&self, branch_name: &str) {
+        info!("{}", tr!(GitCreateTrackingBranch, branch_name));
+        let remote_branch_name = format!("{}/{}", REMOTE_NAME, branch_name);
+        let remote_branch = Self::or_abort(self.repository.find_branch(&remote_branch_name, BranchType::Remote));
+        let commit = Self::or_abort(remote_branch.get().peel_to_commit());
+        let mut branch = Self::or_abort(self.repository.branch(branch_name, &commit, false));
+        Self::or_abort(branch.set_upstream(Some(&remote_branch_name)));
+        Self::record("branch --track ".to_owned() + branch_name);
+    }
+
+    fn read_file(&self, revision: &str, file_path: &str) -> Option<String> {
+        let tree = self.repository.revparse_single(revision).and_then(|object| object.peel_to_tree()).ok()?;
+        let entry = tree.get_path(Path::new(file_path)).ok()?;
+        let blob = self.repository.find_blob(entry.id()).ok()?;
+        String::from_utf8(blob.content().to_vec()).ok()
+    }
+
+    fn get_git_dir(&self) -> Option<PathBuf> {
+        Some(self.repository.path().to_path_buf())
+    }
+
+    fn get_user_name(&self) -> Option<String> {
+        let signature = self.repository.signature().ok()?;
+        let user_name = signature.name().ok()?;
+        match signature.email().ok() {
+            Some(email) => Some(format!("{} <{}>", user_name, email)),
+            None => Some(String::from(user_name)),
+        }
+    }
+
+    fn list_refs(&self) -> BTreeMap<String, String> {
+        let Ok(references) = self.repository.references() else {
+            return BTreeMap::new();
+        };
+        references
+            .flatten()
+            .filter_map(|reference| {
+                let name = reference.name().ok()?;
+                let id = reference.target()?;
+                Some((String::from(name), id.to_string()))
+            })
+            .collect::<BTreeMap<String, String>>()
+    }
+
+    fn delete_branch(&self, branch_name: &str) {
+        info!("{}", tr!(GitDeleteBranch, branch_name));
+        let mut branch = Self::or_abort(self.repository.find_branch(branch_name, BranchType::Local));
+        Self::or_abort(branch.delete());
+        Self::record("branch -D ".to_owned() + branch_name);
+    }
+
+    fn force_branch(&self, branch_name: &str, revision: &str) {
+        info!("{}", tr!(GitForceBranch, branch_name, revision));
+        let object = Self::or_abort(self.repository.revparse_single(revision));
+        if self.get_current_branch_name().as_deref() == Some(branch_name) {
+            Self::or_abort(self.repository.reset(&object, ResetType::Hard, None));
+        } else {
+            let commit = Self::or_abort(object.peel_to_commit());
+            Self::or_abort(self.repository.branch(branch_name, &commit, true));
+        }
+        Self::record("branch --force ".to_owned() + branch_name + " " + revision);
+    }
+
+    fn delete_tag(&self, tag_name: &str) {
+        info!("{}", tr!(GitDeleteTag, tag_name));
+        Self::or_abort(self.repository.tag_delete(tag_name));
+        Self::record("tag --delete ".to_owned() + tag_name);
+    }
+
+    fn force_tag(&self, tag_name: &str, revision: &str) {
+        info!("{}", tr!(GitForceTag, tag_name, revision));
+        let object = Self::or_abort(self.repository.revparse_single(revision));
+        Self::or_abort(self.repository.tag_lightweight(tag_name, &object, true));
+        Self::record("tag --force ".to_owned() + tag_name + " " + revision);
+    }
+
+    /// Walks the history of the branch once instead of checking every tag against it
+    fn list_merged_tags(&self, branch_name: &str) -> Vec<String> {
+        let Ok(branch_commit) = self.repository.revparse_single(branch_name).and_then(|object| object.peel_to_commit()) else {
+            return Vec::new();
+        };
+        let mut revwalk = Self::or_abort(self.repository.revwalk());
+        Self::or_abort(revwalk.push(branch_commit.id()));
+        let reachable = revwalk.flatten().collect::<HashSet<Oid>>();
+        self.list_tags()
+            .into_iter()
+            .filter(|tag_name| {
+                self.repository.revparse_single(&("refs/tags/".to_owned() + tag_name))
+                    .and_then(|object| object.peel_to_commit())
+                    .map(|commit| reachable.contains(&commit.id()))
+                    .unwrap_or(false)
+            })
+            .collect::<Vec<String>>()
+    }
+
+    fn list_remote_tags(&self) -> Vec<String> {
+        let mut remote = Self::or_abort(self.repository.find_remote(REMOTE_NAME));
+        Self::or_abort(remote.connect_auth(Direction::Fetch, Some(self.remote_callbacks()), None));
+        let tag_names = Self::or_abort(remote.list())
+            .iter()
+            .filter_map(|head| head.name().strip_prefix("refs/tags/"))
+            .filter(|tag_name| !tag_name.ends_with("^{}"))
+            .map(String::from)
+            .collect::<Vec<String>>();
+        Self::or_abort(remote.disconnect());
+        tag_names
+    }
+
+    fn delete_remote_branch(&self, branch_name: &str) {
+        info!("{}", tr!(GitDeleteRemoteBranch, branch_name));
+        self.push_refspecs(&[format!(":refs/heads/{}", branch_name)]);
+        let remote_branch_name = format!("{}/{}", REMOTE_NAME, branch_name);
+        if let Ok(mut remote_branch) = self.repository.find_branch(&remote_branch_name, BranchType::Remote) {
+            Self::or_abort(remote_branch.delete());
+        }
+        Self::record("push origin --delete ".to_owned() + branch_name);
+    }
+
+    fn push_branch(&self) {
+        info!("{}", tr!(GitPush));
+        let branch_name = self.current_branch_name();
+        self.push_refspecs(&[format!("refs/heads/{0}:refs/heads/{0}", branch_name)]);
+        Self::record(String::from("push"));
+    }
+
+    fn push_tags(&self) {
+        info!("{}", tr!(GitPushTags));
+        let tag_names = Self::or_abort(self.repository.tag_names(None));
+        let refspecs = tag_names
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|tag_name| format!("refs/tags/{0}:refs/tags/{0}", tag_name))
+            .collect::<Vec<String>>();
+        if !refspecs.is_empty() {
+            self.push_refspecs(&refspecs);
+        }
+        Self::record(String::from("push --tags"));
+    }
+
+    fn push_new_branch_to_remote(&self, branch_name: &str) {
+        info!("{}", tr!(GitPushNewBranch, branch_name));
+        self.push_refspecs(&[format!("refs/heads/{0}:refs/heads/{0}", branch_name)]);
+        let mut branch = Self::or_abort(self.repository.find_branch(branch_name, BranchType::Local));
+        Self::or_abort(branch.set_upstream(Some(format!("{}/{}", REMOTE_NAME, branch_name).as_str())));
+        Self::record("push --set-upstream origin ".to_owned() + branch_name);
+    }
+}
@@ -1,67 +1,83 @@
-use std::process::abort;
+use std::io;
 
 use cmd_lib::{run_cmd, run_fun};
-use colour::{e_blue_ln, e_red_ln};
+use log::info;
+
+use crate::support::audit::AuditLog;
+use crate::support::failure::fail;
+use crate::support::logger::Logger;
+use crate::tr;
 
 pub struct MavenExecution {}
 
 impl MavenExecution {
-    pub fn update_version_to(version: &String) {
-        e_blue_ln!("[MAVEN]Update project's version to new version : {}",version);
-        run_cmd!(mvn versions:set -DnewVersion=$version -DgenerateBackupPoms=false)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
+    pub fn update_version_to(version: &str) {
+        info!("{}", tr!(MavenUpdateVersion, version));
+        let new_version = format!("-DnewVersion={}", version);
+        Self::run_mvn(&["versions:set", &new_version, "-DgenerateBackupPoms=false"]);
     }
 
-    pub fn check_maven_command_exist() {
-        e_blue_ln!("[Verify]Check whether maven is present ...");
-        let result = run_fun!(mvn --version);
-        let maven_version = match result {
-            Ok(output) => Some(String::from(output)),
-            Err(_err) => None,
-        };
-        if maven_version.is_none() {
-            e_red_ln!("Command <mvn> does not exist");
-            abort();
+    pub fn check_maven_command_exist() -> Result<(), String> {
+        info!("{}", tr!(MavenCheckPresent));
+        let result = Logger::timed(&Self::command_line_of(&["--version"]), || run_fun!(mvn --version));
+        Self::record(&["--version"], &result);
+        match result {
+            Ok(maven_version) => {
+                info!("{}", tr!(MavenVersionInfo, maven_version));
+                Ok(())
+            }
+            Err(_) => Err(tr!(MavenNotFound)),
         }
-        e_blue_ln!("Current maven version info : \n {}",maven_version.unwrap())
     }
 
-    pub fn verify_project() {
-        e_blue_ln!("[MAVEN]Verify current maven project");
-        run_cmd!(mvn clean package -DskipTests -U)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
-        run_cmd!(mvn clean)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
+    pub fn verify_project(run_tests: bool) -> Result<(), String> {
+        info!("{}", tr!(MavenVerify));
+        let arguments: &[&str] = if run_tests {
+            &["clean", "package", "-U"]
+        } else {
+            &["clean", "package", "-DskipTests", "-U"]
+        };
+        let result = Logger::timed(&Self::command_line_of(arguments), || run_cmd!(mvn $[arguments] 1>&2));
+        Self::record(arguments, &result);
+        if let Err(err) = result {
+            return Err(tr!(CommandFailed, "mvn ".to_owned() + arguments.join(" ").as_str(), err));
+        }
+        Self::run_mvn(&["clean"]);
+        Ok(())
     }
 
     pub fn update_property_version(property_name: String, property_version: String) {
-        e_blue_ln!("[MAVEN]Update property's version ,property's name : {} , new property value : {}",property_name,property_version);
-        run_cmd!(mvn versions:set-property -Dproperty=$property_name -DnewVersion=$property_version -DgenerateBackupPoms=false)
-            .map_err(|err| {
-                e_red_ln!("{:?}", err);
-                abort();
-            })
-            .ok();
+        info!("{}", tr!(MavenUpdateProperty, property_name, property_version));
+        let property = format!("-Dproperty={}", property_name);
+        let new_version = format!("-DnewVersion={}", property_version);
+        Self::run_mvn(&["versions:set-property", &property, &new_version, "-DgenerateBackupPoms=false"]);
     }
 
     pub fn get_current_project_version() -> Option<String> {
-        e_blue_ln!("[MAVEN]Get current project's version");
-        let result = run_fun!(mvn -q -Dexec.executable=echo -Dexec.args="$(project.version)" --non-recursive exec:exec);
-        match result {
-            Ok(output) => Some(String::from(output)),
-            Err(_err) => None,
+        info!("{}", tr!(MavenGetVersion));
+        let arguments = ["-q", "-Dexec.executable=echo", "-Dexec.args=${project.version}", "--non-recursive", "exec:exec"];
+        let result = Logger::timed(
+            &Self::command_line_of(&arguments),
+            || run_fun!(mvn -q -Dexec.executable=echo -Dexec.args="$(project.version)" --non-recursive exec:exec),
+        );
+        Self::record(&arguments, &result);
+        result.ok()
+    }
+
+    /// Runs maven and records it into the audit log, aborts if maven fails
+    fn run_mvn(arguments: &[&str]) {
+        let result = Logger::timed(&Self::command_line_of(arguments), || run_cmd!(mvn $[arguments] 1>&2));
+        Self::record(arguments, &result);
+        if let Err(err) = result {
+            fail(&tr!(CommandFailed, Self::command_line_of(arguments), err));
         }
     }
-}
\ No newline at end of file
+
+    fn record<T>(arguments: &[&str], result: &io::Result<T>) {
+        AuditLog::record_command(Self::command_line_of(arguments), AuditLog::exit_code_of(result));
+    }
+
+    fn command_line_of(arguments: &[&str]) -> String {
+        "mvn ".to_owned() + arguments.join(" ").as_str()
+    }
+}
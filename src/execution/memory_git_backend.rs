@@ -0,0 +1,438 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::execution::git_backend::GitBackend;
+use crate::support::enums::VerificationLevel;
+
+/// A commit of the in-memory repository, the project version is the only tracked file
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryCommit {
+    pub id: usize,
+    pub parents: Vec<usize>,
+    pub message: String,
+    pub version: String,
+}
+
+/// In-memory repository model with a single `origin` remote
+#[derive(Debug)]
+pub struct MemoryRepository {
+    pub commits: Vec<MemoryCommit>,
+    pub branches: BTreeMap<String, usize>,
+    pub remote_branches: BTreeMap<String, usize>,
+    pub tags: BTreeMap<String, usize>,
+    pub remote_tags: BTreeSet<String>,
+    pub current_branch: String,
+    pub working_version: String,
+    /// The working version and the level of each project verification
+    pub verifications: Vec<(String, VerificationLevel)>,
+}
+
+impl MemoryRepository {
+    /// Creates a repository whose `master` branch has one commit with the initial version,
+    /// the branch is pushed to `origin`
+    pub fn new(initial_version: &str) -> MemoryRepository {
+        let initial_commit = MemoryCommit {
+            id: 0,
+            parents: Vec::new(),
+            message: String::from("initial commit"),
+            version: String::from(initial_version),
+        };
+        let mut branches = BTreeMap::new();
+        branches.insert(String::from("master"), 0);
+        MemoryRepository {
+            commits: vec![initial_commit],
+            remote_branches: branches.clone(),
+            branches,
+            tags: BTreeMap::new(),
+            remote_tags: BTreeSet::new(),
+            current_branch: String::from("master"),
+            working_version: String::from(initial_version),
+            verifications: Vec::new(),
+        }
+    }
+
+    pub fn shared(self) -> Rc<RefCell<MemoryRepository>> {
+        Rc::new(RefCell::new(self))
+    }
+
+    /// Creates a local branch from the head of another local branch
+    pub fn with_branch(mut self, branch_name: &str, from_branch_name: &str) -> MemoryRepository {
+        let head = self.branch_head(from_branch_name);
+        self.branches.insert(String::from(branch_name), head);
+        self
+    }
+
+    /// Adds a commit with the version on top of the local branch
+    pub fn with_commit(mut self, branch_name: &str, message: &str, version: &str) -> MemoryRepository {
+        let head = self.branch_head(branch_name);
+        let id = self.add_commit(vec![head], message, version);
+        self.branches.insert(String::from(branch_name), id);
+        if self.current_branch == branch_name {
+            self.working_version = String::from(version);
+        }
+        self
+    }
+
+    /// Tags the head of the local branch
+    pub fn with_tag(mut self, tag_name: &str, branch_name: &str) -> MemoryRepository {
+        let head = self.branch_head(branch_name);
+        self.tags.insert(String::from(tag_name), head);
+        self
+    }
+
+    /// Pushes every local branch and tag to `origin`
+    pub fn with_everything_pushed(mut self) -> MemoryRepository {
+        self.remote_branches = self.branches.clone();
+        self.remote_tags = self.tags.keys().cloned().collect();
+        self
+    }
+
+    /// Removes the local branch, only its `origin` counterpart is kept
+    pub fn with_remote_only(mut self, branch_name: &str) -> MemoryRepository {
+        let head = self.branch_head(branch_name);
+        self.remote_branches.insert(String::from(branch_name), head);
+        self.branches.remove(branch_name);
+        self
+    }
+
+    pub fn branch_head(&self, branch_name: &str) -> usize {
+        match self.branches.get(branch_name) {
+            Some(head) => *head,
+            None => panic!("Branch doesn't exist : {}", branch_name),
+        }
+    }
+
+    pub fn head_commit(&self, branch_name: &str) -> &MemoryCommit {
+        &self.commits[self.branch_head(branch_name)]
+    }
+
+    pub fn tag_commit(&self, tag_name: &str) -> Option<&MemoryCommit> {
+        self.tags.get(tag_name).map(|id| &self.commits[*id])
+    }
+
+    /// Whether the commit is reachable from the other commit
+    pub fn is_ancestor(&self, ancestor: usize, descendant: usize) -> bool {
+        self.ancestors(descendant).contains(&ancestor)
+    }
+
+    fn ancestors(&self, id: usize) -> BTreeSet<usize> {
+        let mut visited = BTreeSet::new();
+        let mut pending = vec![id];
+        while let Some(current) = pending.pop() {
+            if visited.insert(current) {
+                pending.extend(self.commits[current].parents.iter());
+            }
+        }
+        visited
+    }
+
+    /// Resolves a local branch, an `origin/` remote branch, a tag or a commit id
+    pub fn resolve(&self, revision: &str) -> Option<usize> {
+        if let Some(id) = self.branches.get(revision) {
+            return Some(*id);
+        }
+        if let Some(id) = revision.strip_prefix("origin/").and_then(|name| self.remote_branches.get(name)) {
+            return Some(*id);
+        }
+        if let Some(id) = self.tags.get(revision) {
+            return Some(*id);
+        }
+        revision.parse::<usize>().ok().filter(|id| *id < self.commits.len())
+    }
+
+    fn merge_base(&self, left: usize, right: usize) -> Option<usize> {
+        let left_ancestors = self.ancestors(left);
+        self.ancestors(right)
+            .intersection(&left_ancestors)
+            .max()
+            .copied()
+    }
+
+    fn add_commit(&mut self, parents: Vec<usize>, message: &str, version: &str) -> usize {
+        let id = self.commits.len();
+        self.commits.push(MemoryCommit {
+            id,
+            parents,
+            message: String::from(message),
+            version: String::from(version),
+        });
+        id
+    }
+
+    fn checkout(&mut self, branch_name: &str) {
+        let target_version = self.head_commit(branch_name).version.clone();
+        let current_version = self.head_commit(&self.current_branch.clone()).version.clone();
+        if self.working_version != current_version && target_version != current_version {
+            panic!("Local changes would be overwritten by checkout : {}", branch_name);
+        }
+        if self.working_version == current_version {
+            self.working_version = target_version;
+        }
+        self.current_branch = String::from(branch_name);
+    }
+
+    fn merge(&mut self, source: usize, message: &str) {
+        let current_branch = self.current_branch.clone();
+        let current = self.branch_head(&current_branch);
+        if self.is_ancestor(source, current) {
+            return;
+        }
+        if self.is_ancestor(current, source) {
+            self.branches.insert(current_branch, source);
+            self.working_version = self.commits[source].version.clone();
+            return;
+        }
+        let base_version = self
+            .merge_base(current, source)
+            .map(|base| self.commits[base].version.clone());
+        let current_version = self.commits[current].version.clone();
+        let source_version = self.commits[source].version.clone();
+        let merged_version = if Some(&current_version) == base_version.as_ref() {
+            source_version
+        } else if Some(&source_version) == base_version.as_ref() || source_version == current_version {
+            current_version
+        } else {
+            panic!(
+                "Merge conflict on version : {} <> {}",
+                current_version, source_version
+            );
+        };
+        let id = self.add_commit(vec![current, source], message, &merged_version);
+        self.branches.insert(current_branch, id);
+        self.working_version = merged_version;
+    }
+}
+
+/// Git backend operating on a shared [MemoryRepository]
+pub struct MemoryGitBackend {
+    pub repository: Rc<RefCell<MemoryRepository>>,
+}
+
+impl MemoryGitBackend {
+    pub fn new(repository: Rc<RefCell<MemoryRepository>>) -> MemoryGitBackend {
+        MemoryGitBackend { repository }
+    }
+}
+
+impl GitBackend for MemoryGitBackend {
+    fn fetch(&self) {}
+
+    fn pull(&self) {
+        let mut repository = self.repository.borrow_mut();
+        let current_branch = repository.current_branch.clone();
+        let upstream = match repository.remote_branches.get(&current_branch) {
+            Some(upstream) => *upstream,
+            None => panic!("There is no tracking information for the current branch : {}", current_branch),
+        };
+        let message = format!("Merge branch '{}' of origin", current_branch);
+        repository.merge(upstream, &message);
+    }
+
+    fn switch_branch(&self, branch_name: &str) {
+        let mut repository = self.repository.borrow_mut();
+        if !repository.branches.contains_key(branch_name) {
+            drop(repository);
+            self.create_tracking_branch(branch_name);
+            repository = self.repository.borrow_mut();
+        }
+        repository.checkout(branch_name);
+    }
+
+    fn checkout_branch(&self, branch_name: &str) {
+        self.repository.borrow_mut().checkout(branch_name);
+    }
+
+    fn create_new_branch_from_current(&self, branch_name: &str) {
+        let mut repository = self.repository.borrow_mut();
+        if repository.branches.contains_key(branch_name) {
+            panic!("A branch named '{}' already exists", branch_name);
+        }
+        let head = repository.branch_head(&repository.current_branch.clone());
+        repository.branches.insert(String::from(branch_name), head);
+    }
+
+    fn merge_to_current(&self, source_branch_name: &str) {
+        let mut repository = self.repository.borrow_mut();
+        let source = repository.branch_head(source_branch_name);
+        let message = format!("Merge branch '{}'", source_branch_name);
+        repository.merge(source, &message);
+    }
+
+    fn are_there_anything_changed(&self) -> bool {
+        let repository = self.repository.borrow();
+        repository.working_version != repository.head_commit(&repository.current_branch).version
+    }
+
+    /// The version file is the only tracked file, its content stands for its object id
+    fn list_changed_files(&self) -> BTreeMap<String, String> {
+        let repository = self.repository.borrow();
+        let mut changed_files = BTreeMap::new();
+        if repository.working_version != repository.head_commit(&repository.current_branch).version {
+            changed_files.insert(String::from("version"), repository.working_version.clone());
+        }
+        changed_files
+    }
+
+    fn commit(&self, message: &str) {
+        let mut repository = self.repository.borrow_mut();
+        let current_branch = repository.current_branch.clone();
+        let head = repository.branch_head(&current_branch);
+        let version = repository.working_version.clone();
+        let id = repository.add_commit(vec![head], message, &version);
+        repository.branches.insert(current_branch, id);
+    }
+
+    fn tag(&self, tag_name: &str) {
+        let mut repository = self.repository.borrow_mut();
+        if repository.tags.contains_key(tag_name) {
+            panic!("Tag '{}' already exists", tag_name);
+        }
+        let head = repository.branch_head(&repository.current_branch.clone());
+        repository.tags.insert(String::from(tag_name), head);
+    }
+
+    fn is_branch_exists(&self, branch_name: &str, is_remote: bool) -> bool {
+        let repository = self.repository.borrow();
+        if is_remote {
+            repository.remote_branches.contains_key(branch_name)
+        } else {
+            repository.branches.contains_key(branch_name)
+        }
+    }
+
+    fn list_all_branch(&self) -> Vec<String> {
+        self.repository.borrow().branches.keys().cloned().collect()
+    }
+
+    fn list_remote_branch(&self) -> Vec<String> {
+        self.repository.borrow().remote_branches.keys().cloned().collect()
+    }
+
+    fn get_current_branch_name(&self) -> Option<String> {
+        Some(self.repository.borrow().current_branch.clone())
+    }
+
+    fn get_ahead_behind(&self, revision: &str, base: &str) -> Option<(usize, usize)> {
+        let repository = self.repository.borrow();
+        let revision_ancestors = repository.ancestors(repository.resolve(revision)?);
+        let base_ancestors = repository.ancestors(repository.resolve(base)?);
+        Some((
+            revision_ancestors.difference(&base_ancestors).count(),
+            base_ancestors.difference(&revision_ancestors).count(),
+        ))
+    }
+
+    /// Commits are created in order, the commit id stands for its time
+    fn get_commit_time(&self, revision: &str) -> Option<i64> {
+        self.repository.borrow().resolve(revision).map(|id| id as i64)
+    }
+
+    fn create_tracking_branch(&self, branch_name: &str) {
+        let mut repository = self.repository.borrow_mut();
+        let remote_head = match repository.remote_branches.get(branch_name) {
+            Some(remote_head) => *remote_head,
+            None => panic!("Invalid reference : origin/{}", branch_name),
+        };
+        repository.branches.insert(String::from(branch_name), remote_head);
+    }
+
+    /// The version is the only file content of the in-memory repository
+    fn read_file(&self, revision: &str, _file_path: &str) -> Option<String> {
+        let repository = self.repository.borrow();
+        let id = repository.resolve(revision)?;
+        Some(repository.commits[id].version.clone())
+    }
+
+    /// There is no git directory, nothing is written by the audit log
+    fn get_git_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn get_user_name(&self) -> Option<String> {
+        None
+    }
+
+    fn list_refs(&self) -> BTreeMap<String, String> {
+        let repository = self.repository.borrow();
+        let branches = repository.branches.iter()
+            .map(|(name, id)| (format!("refs/heads/{}", name), id.to_string()));
+        let remote_branches = repository.remote_branches.iter()
+            .map(|(name, id)| (format!("refs/remotes/origin/{}", name), id.to_string()));
+        let tags = repository.tags.iter()
+            .map(|(name, id)| (format!("refs/tags/{}", name), id.to_string()));
+        branches.chain(remote_branches).chain(tags).collect::<BTreeMap<String, String>>()
+    }
+
+    fn delete_branch(&self, branch_name: &str) {
+        let mut repository = self.repository.borrow_mut();
+        if repository.current_branch == branch_name {
+            panic!("Cannot delete branch '{}' checked out", branch_name);
+        }
+        repository.branches.remove(branch_name);
+    }
+
+    fn force_branch(&self, branch_name: &str, revision: &str) {
+        let mut repository = self.repository.borrow_mut();
+        let id = match repository.resolve(revision) {
+            Some(id) => id,
+            None => panic!("Invalid reference : {}", revision),
+        };
+        repository.branches.insert(String::from(branch_name), id);
+        if repository.current_branch == branch_name {
+            repository.working_version = repository.commits[id].version.clone();
+        }
+    }
+
+    fn delete_tag(&self, tag_name: &str) {
+        self.repository.borrow_mut().tags.remove(tag_name);
+    }
+
+    fn force_tag(&self, tag_name: &str, revision: &str) {
+        let mut repository = self.repository.borrow_mut();
+        let id = match repository.resolve(revision) {
+            Some(id) => id,
+            None => panic!("Invalid reference : {}", revision),
+        };
+        repository.tags.insert(String::from(tag_name), id);
+    }
+
+    fn list_merged_tags(&self, branch_name: &str) -> Vec<String> {
+        let repository = self.repository.borrow();
+        let Some(branch_head) = repository.resolve(branch_name) else {
+            return Vec::new();
+        };
+        let ancestors = repository.ancestors(branch_head);
+        repository.tags.iter()
+            .filter(|(_, id)| ancestors.contains(id))
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<String>>()
+    }
+
+    fn list_remote_tags(&self) -> Vec<String> {
+        self.repository.borrow().remote_tags.iter().cloned().collect::<Vec<String>>()
+    }
+
+    fn delete_remote_branch(&self, branch_name: &str) {
+        self.repository.borrow_mut().remote_branches.remove(branch_name);
+    }
+
+    fn push_branch(&self) {
+        let mut repository = self.repository.borrow_mut();
+        let current_branch = repository.current_branch.clone();
+        let head = repository.branch_head(&current_branch);
+        repository.remote_branches.insert(current_branch, head);
+    }
+
+    fn push_tags(&self) {
+        let mut repository = self.repository.borrow_mut();
+        repository.remote_tags = repository.tags.keys().cloned().collect();
+    }
+
+    fn push_new_branch_to_remote(&self, branch_name: &str) {
+        let mut repository = self.repository.borrow_mut();
+        let head = repository.branch_head(branch_name);
+        repository.remote_branches.insert(String::from(branch_name), head);
+    }
+}
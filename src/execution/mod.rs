@@ -1,2 +1,9 @@
 pub mod git_execution;
-pub mod maven_execution;
\ No newline at end of file
+pub mod git_failure;
+pub mod maven_execution;
+pub mod git_backend;
+pub mod cli_git_backend;
+pub mod libgit2_git_backend;
+pub mod memory_git_backend;
+pub mod hook_execution;
+pub mod node_execution;
@@ -0,0 +1,117 @@
+use std::io;
+use std::path::Path;
+
+use cmd_lib::{run_cmd, run_fun};
+use log::info;
+
+use crate::support::audit::AuditLog;
+use crate::support::logger::Logger;
+use crate::support::enums::PackageManager;
+use crate::tr;
+
+pub struct NodeExecution {}
+
+impl NodeExecution {
+    /// The package manager of the project in the directory, detected from its lockfile
+    pub fn detect_package_manager(project_dir: &Path) -> PackageManager {
+        if project_dir.join("pnpm-lock.yaml").exists() {
+            PackageManager::Pnpm
+        } else if project_dir.join("yarn.lock").exists() {
+            PackageManager::Yarn
+        } else {
+            PackageManager::Npm
+        }
+    }
+
+    pub fn command_of(package_manager: &PackageManager) -> &'static str {
+        match package_manager {
+            PackageManager::Npm => "npm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Pnpm => "pnpm",
+        }
+    }
+
+    /// Install arguments which fail instead of updating an outdated lockfile
+    pub fn clean_install_arguments(package_manager: &PackageManager, project_dir: &Path) -> Vec<&'static str> {
+        match package_manager {
+            PackageManager::Npm if project_dir.join("package-lock.json").exists()
+                || project_dir.join("npm-shrinkwrap.json").exists() => vec!["ci"],
+            // without lockfile nothing is frozen, and none is written into the release commit
+            PackageManager::Npm => vec!["install", "--no-package-lock"],
+            // yarn 2+ is configured by .yarnrc.yml and renamed the option
+            PackageManager::Yarn if project_dir.join(".yarnrc.yml").exists() => vec!["install", "--immutable"],
+            PackageManager::Yarn => vec!["install", "--frozen-lockfile"],
+            PackageManager::Pnpm => vec!["install", "--frozen-lockfile"],
+        }
+    }
+
+    pub fn check_command_exist(package_manager: &PackageManager) -> Result<(), String> {
+        let command = Self::command_of(package_manager);
+        info!("{}", tr!(NodeCheckPresent, command));
+        let result = Logger::timed(&Self::command_line_of(command, &["--version"]), || run_fun!($command --version));
+        Self::record(command, &["--version"], &result);
+        match result {
+            Ok(version) => {
+                info!("{}", tr!(NodeVersionInfo, command, version));
+                Ok(())
+            }
+            Err(_) => Err(tr!(NodeCommandNotFound, command)),
+        }
+    }
+
+    pub fn clean_install(package_manager: &PackageManager, project_dir: &Path) -> Result<(), String> {
+        info!("{}", tr!(NodeCleanInstall, package_manager));
+        Self::run(package_manager, &Self::clean_install_arguments(package_manager, project_dir))
+    }
+
+    pub fn run_script(package_manager: &PackageManager, script: &str) -> Result<(), String> {
+        info!("{}", tr!(NodeRunScript, script));
+        Self::run(package_manager, &["run", script])
+    }
+
+    fn run(package_manager: &PackageManager, arguments: &[&str]) -> Result<(), String> {
+        let command = Self::command_of(package_manager);
+        let command_line = Self::command_line_of(command, arguments);
+        let result = Logger::timed(&command_line, || run_cmd!($command $[arguments] 1>&2));
+        Self::record(command, arguments, &result);
+        result.map_err(|err| {
+            match AuditLog::exit_code_of(&Err::<(), io::Error>(err)) {
+                Some(exit_code) => tr!(NodeCommandExited, command_line, exit_code),
+                None => tr!(NodeCommandNotRun, command_line),
+            }
+        })
+    }
+
+    fn record<T>(command: &str, arguments: &[&str], result: &io::Result<T>) {
+        AuditLog::record_command(Self::command_line_of(command, arguments), AuditLog::exit_code_of(result));
+    }
+
+    fn command_line_of(command: &str, arguments: &[&str]) -> String {
+        command.to_owned() + " " + arguments.join(" ").as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::execution::node_execution::NodeExecution;
+    use crate::support::enums::PackageManager;
+
+    #[test]
+    fn detect_package_manager_from_lockfile() {
+        let project_dir = tempfile::tempdir().unwrap();
+        let path = project_dir.path();
+        assert_eq!(PackageManager::Npm, NodeExecution::detect_package_manager(path));
+        assert_eq!(vec!["install", "--no-package-lock"], NodeExecution::clean_install_arguments(&PackageManager::Npm, path));
+        fs::write(path.join("package-lock.json"), "{}").unwrap();
+        assert_eq!(vec!["ci"], NodeExecution::clean_install_arguments(&PackageManager::Npm, path));
+        fs::write(path.join("yarn.lock"), "").unwrap();
+        assert_eq!(PackageManager::Yarn, NodeExecution::detect_package_manager(path));
+        assert_eq!(vec!["install", "--frozen-lockfile"], NodeExecution::clean_install_arguments(&PackageManager::Yarn, path));
+        fs::write(path.join(".yarnrc.yml"), "").unwrap();
+        assert_eq!(vec!["install", "--immutable"], NodeExecution::clean_install_arguments(&PackageManager::Yarn, path));
+        fs::write(path.join("pnpm-lock.yaml"), "").unwrap();
+        assert_eq!(PackageManager::Pnpm, NodeExecution::detect_package_manager(path));
+    }
+}
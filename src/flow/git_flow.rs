@@ -1,4 +1,4 @@
-use crate::support::enums::ProjectType;
+use crate::support::enums::{ProjectType, VerificationLevel};
 
 pub trait GitFlow {
     /// The master branch name
@@ -11,18 +11,36 @@ pub trait GitFlow {
     const HOTFIX_BRANCH: &'static str = "hotfix";
     /// The test branch name
     const TEST_BRANCH: &'static str = "test";
+    /// The support branch name
+    const SUPPORT_BRANCH: &'static str = "support";
     /// Current ProjectType
     fn project_type(&self) -> ProjectType;
     /// Verify current project
-    fn verify_project(&self);
+    fn verify_project(&self, verification_level: &VerificationLevel) -> Result<(), String>;
     /// Do feature flow
     fn do_feature(&self);
+    /// Do feature flow of the next major version from develop
+    fn do_major_feature(&self);
     /// Do hotfix flow
     fn do_hotfix(&self);
     /// Do release flow from test branch
     fn do_release_test(&self);
+    /// Do release flow of the next major version from test branch
+    fn do_release_major(&self);
+    /// Do release candidate flow on the test branch of the next release, or the next major release
+    fn do_release_candidate(&self, major: bool);
     /// Do release flow from specific branch
     fn do_release_specific(&self,release_source_branch: String);
     /// Do release flow from hotfix branch
     fn do_release_hotfix(&self);
+    /// Do hotfix flow from the release tag, on the minor line of the tag
+    fn do_hotfix_from_tag(&self, base_tag_name: String);
+    /// Do release flow from the hotfix branch of the release tag's minor line, without merging into master or develop
+    fn do_release_hotfix_from_tag(&self, base_tag_name: String);
+    /// Do support flow, creates the support branch of the release tag's minor line
+    fn do_support(&self, base_tag_name: String);
+    /// Do hotfix flow from the support branch
+    fn do_hotfix_on_support(&self, support_branch_name: String);
+    /// Do release flow from the hotfix branch into the support branch, without touching master or develop
+    fn do_release_hotfix_on_support(&self, support_branch_name: String);
 }
\ No newline at end of file
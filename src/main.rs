@@ -1,123 +1,230 @@
-use std::process::abort;
+use std::io::{self, IsTerminal};
 
-use colour::e_red_ln;
+use clap::Parser;
+use log::error;
 
 use crate::action::common_git_flow_action::CommonGitFlowAction;
+use crate::command::cleanup_command::CleanupCommand;
+use crate::command::history_command::HistoryCommand;
+use crate::command::status_command::StatusCommand;
+use crate::command::undo_command::UndoCommand;
+use crate::command::verify_versions_command::VerifyVersionsCommand;
 use crate::execution::git_execution::{GitExecution, MIN_GIT_VERSION};
 use crate::flow::git_flow::GitFlow;
-use crate::support::enums::{FlowType, ReleaseType};
-use crate::support::parameter::GitFlowParameter;
+use crate::support::argument::{Arguments, Command};
+use crate::support::enums::{FlowType, GitBackendType, ProjectType, ReleaseType};
+use crate::support::audit::AuditLog;
+use crate::support::config::GitFlowConfig;
+use crate::support::failure::fail;
+use crate::support::i18n::init_language;
+use crate::support::logger::Logger;
+use crate::support::parameter::{git_backend_of, git_flow_action_adapter_of, GitFlowParameter};
 use crate::support::prompt::{confirm_execution_prompt, get_branch_name_from_select, get_flow_type_from_prompt, get_release_type_from_prompt};
-use crate::support::prompt::get_package_type_from_prompt;
+use crate::support::prompt::{get_package_type_from_prompt, select_release_tag, select_support_branch};
+use crate::support::report::FlowReporter;
+use crate::support::version::init_tag_format;
 
 pub mod execution;
 pub mod support;
 pub mod flow;
 pub mod action;
+pub mod command;
 
 fn main() {
-    let current_git_version = GitExecution::get_git_version();
-    if current_git_version.is_none() {
-        e_red_ln!("未在本机器识别到git命令");
-        return;
+    let arguments = Arguments::parse();
+    init_language(arguments.lang.clone());
+    if let Err(message) = Logger::init(Logger::level_of(arguments.quiet, arguments.verbose), arguments.log_file.as_deref()) {
+        fail(&message);
     }
+    if GitBackendType::Cli.eq(&arguments.git_backend) {
+        let current_git_version = GitExecution::get_git_version();
+        if current_git_version.is_none() {
+            error!("{}", tr!(GitNotFound));
+            return;
+        }
 
-    let is_valid_git_version =
-        GitExecution::check_git_version(&current_git_version.unwrap());
-    if !is_valid_git_version {
-        e_red_ln!("本机git命令，版本过低，最小版本 :{}", MIN_GIT_VERSION);
-        return;
+        let is_valid_git_version =
+            GitExecution::check_git_version(&current_git_version.unwrap());
+        if !is_valid_git_version {
+            error!("{}", tr!(GitVersionTooLow, MIN_GIT_VERSION));
+            return;
+        }
     }
-    let result = get_package_type_from_prompt();
-    let project_type = match result {
-        Ok(choice) => choice,
-        Err(_) => {
-            e_red_ln!("未识别的项目类型，请重试");
-            abort();
+    if arguments.command.is_none() {
+        FlowReporter::start(&arguments.output);
+    }
+    // nothing can be prompted without a terminal, so every input must be given
+    if !io::stdin().is_terminal() {
+        let missing_inputs = arguments.missing_inputs();
+        if !missing_inputs.is_empty() {
+            fail(&tr!(MissingInputs, missing_inputs.join(" , ")));
+        }
+    }
+    let mut config = GitFlowConfig::load();
+    init_tag_format(config.release.tag_format.clone());
+    if let Some(command) = arguments.command {
+        match command {
+            Command::Status => {
+                let project_type = get_project_type(arguments.project_type);
+                let status_command = StatusCommand::new(
+                    git_flow_action_adapter_of(&project_type),
+                    git_backend_of(&arguments.git_backend),
+                    config.release,
+                );
+                status_command.execute();
+            }
+            Command::Cleanup { dry_run } => {
+                let cleanup_command = CleanupCommand::new(git_backend_of(&arguments.git_backend));
+                cleanup_command.execute(dry_run, arguments.yes);
+            }
+            Command::History { limit } => {
+                let history_command = HistoryCommand::new(git_backend_of(&arguments.git_backend));
+                history_command.execute(limit);
+            }
+            Command::Undo => {
+                let undo_command = UndoCommand::new(git_backend_of(&arguments.git_backend));
+                undo_command.execute(arguments.yes);
+            }
+            Command::VerifyVersions => {
+                let project_type = get_project_type(arguments.project_type);
+                let verify_versions_command = VerifyVersionsCommand::new(
+                    git_flow_action_adapter_of(&project_type),
+                    git_backend_of(&arguments.git_backend),
+                    config.release,
+                );
+                verify_versions_command.execute();
+            }
         }
+        return;
+    }
+    let project_type = get_project_type(arguments.project_type);
+    let result = match arguments.flow_type {
+        Some(flow_type) => Ok(flow_type),
+        None => get_flow_type_from_prompt(),
     };
-    let result = get_flow_type_from_prompt();
     let flow_type = match result {
         Ok(choice) => choice,
-        Err(_) => {
-            e_red_ln!("未识别的流程类型，请重试");
-            abort();
-        }
+        Err(_) => fail(&tr!(InvalidFlowType)),
     };
     let mut git_flow_parameter = GitFlowParameter::new(project_type, flow_type);
+    git_flow_parameter.set_git_backend_type(arguments.git_backend);
+    let git_backend = git_flow_parameter.get_git_backend();
     if FlowType::Release.eq(&git_flow_parameter.flow_type) {
-        let release_type_result = get_release_type_from_prompt();
+        let release_type_result = match arguments.release_type {
+            Some(release_type) => Ok(release_type),
+            None => get_release_type_from_prompt(),
+        };
         let result_type = match release_type_result {
             Ok(choice) => choice,
-            Err(_) => {
-                e_red_ln!("未识别的Release类型，请重试");
-                abort();
-            }
+            Err(_) => fail(&tr!(InvalidReleaseType)),
         };
         if ReleaseType::Specific.eq(&result_type) {
-            let specific_branch_name_result = get_branch_name_from_select();
+            let specific_branch_name_result = match arguments.specific_branch {
+                Some(specific_branch) => Ok(specific_branch),
+                None => get_branch_name_from_select(
+                    git_flow_parameter.get_git_flow_action_adapter().as_ref(),
+                    git_backend.as_ref(),
+                ),
+            };
             let specific_branch_name = match specific_branch_name_result {
                 Ok(choice) => choice,
-                Err(_) => {
-                    e_red_ln!("未识别的要指定的合并分支，请重试");
-                    abort();
-                }
+                Err(_) => fail(&tr!(InvalidSpecificBranch)),
             };
             git_flow_parameter.set_specific_release_branch_name(specific_branch_name);
         }
         git_flow_parameter.set_release_type(result_type);
     }
-    // let push_to_remote_result = select_true_or_false("是否Push分支到远端?");
-    // let push_to_remote = match push_to_remote_result {
-    //     Ok(choice) => choice,
-    //     Err(_) => {
-    //         e_red_ln!("未识别的选择项，请重试");
-    //         abort();
-    //     }
-    // };
-    // let push_tag_to_remote_result = select_true_or_false("是否Push所有Tag到远端?");
-    // let push_tag_to_remote = match push_tag_to_remote_result {
-    //     Ok(choice) => choice,
-    //     Err(_) => {
-    //         e_red_ln!("未识别的选择项，请重试");
-    //         abort();
-    //     }
-    // };
-    // git_flow_parameter.set_need_push(push_to_remote);
-    // git_flow_parameter.set_need_push_tag(push_tag_to_remote);
+    if arguments.major {
+        let is_major_flow = FlowType::Feature.eq(&git_flow_parameter.flow_type)
+            || Some(ReleaseType::Test) == git_flow_parameter.release_type
+            || Some(ReleaseType::Candidate) == git_flow_parameter.release_type;
+        if !is_major_flow {
+            fail(&tr!(MajorFlowOnly));
+        }
+        git_flow_parameter.set_major(true);
+    }
+    let is_hotfix = FlowType::Hotfix.eq(&git_flow_parameter.flow_type)
+        || Some(ReleaseType::Hotfix) == git_flow_parameter.release_type;
+    let is_support = FlowType::Support.eq(&git_flow_parameter.flow_type);
+    if let (true, Some(support_branch)) = (is_hotfix, arguments.support_branch) {
+        let support_branch_result = if support_branch.is_empty() {
+            select_support_branch(git_backend.as_ref())
+        } else {
+            Ok(support_branch)
+        };
+        let support_branch_name = match support_branch_result {
+            Ok(choice) => choice,
+            Err(_) => fail(&tr!(InvalidSupportBranch)),
+        };
+        git_flow_parameter.set_support_branch_name(support_branch_name);
+    } else if (is_hotfix && arguments.from_tag.is_some()) || is_support {
+        let from_tag = arguments.from_tag.unwrap_or_default();
+        let base_tag_result = if from_tag.is_empty() {
+            select_release_tag(git_backend.as_ref())
+        } else {
+            Ok(from_tag)
+        };
+        let base_tag = match base_tag_result {
+            Ok(choice) => choice,
+            Err(_) => fail(&tr!(InvalidBaseTag)),
+        };
+        git_flow_parameter.set_base_tag(base_tag);
+    }
+    if let Some(verification_level) = arguments.verification {
+        config.release.verification = verification_level;
+    }
+    if let Err(message) = config.validate_version_replacers() {
+        fail(&message);
+    }
     git_flow_parameter.print_parameters();
-    let confirm_result = confirm_execution_prompt();
-    let confirm = match confirm_result {
-        Ok(value) => value,
-        Err(_) => false
+    let confirm_result = if arguments.yes {
+        Ok(true)
+    } else {
+        confirm_execution_prompt()
     };
+    let confirm = confirm_result.unwrap_or_default();
     if !confirm {
-        e_red_ln!("已取消任务。");
-        abort();
+        fail(&tr!(Cancelled));
     }
+    AuditLog::start(&git_flow_parameter, git_backend.as_ref());
+    FlowReporter::start_flow(&git_flow_parameter, git_backend.as_ref());
     let git_flow_action_adapter = git_flow_parameter.get_git_flow_action_adapter();
-    let common_git_flow_action = CommonGitFlowAction::new(git_flow_action_adapter);
-    if FlowType::Feature.eq(&git_flow_parameter.flow_type) {
-        common_git_flow_action.do_feature();
-        return;
-    }
-    if FlowType::Hotfix.eq(&git_flow_parameter.flow_type) {
-        common_git_flow_action.do_hotfix();
-        return;
+    let common_git_flow_action = CommonGitFlowAction::new(git_flow_action_adapter, git_backend, config);
+    match git_flow_parameter.flow_type {
+        FlowType::Feature if git_flow_parameter.major => common_git_flow_action.do_major_feature(),
+        FlowType::Feature => common_git_flow_action.do_feature(),
+        FlowType::Hotfix => match (git_flow_parameter.support_branch_name.clone(), git_flow_parameter.base_tag.clone()) {
+            (Some(support_branch_name), _) => common_git_flow_action.do_hotfix_on_support(support_branch_name),
+            (None, Some(base_tag)) => common_git_flow_action.do_hotfix_from_tag(base_tag),
+            (None, None) => common_git_flow_action.do_hotfix(),
+        },
+        FlowType::Support => common_git_flow_action.do_support(git_flow_parameter.base_tag.clone().unwrap()),
+        FlowType::Release => match git_flow_parameter.release_type.clone().unwrap() {
+            ReleaseType::Test if git_flow_parameter.major => common_git_flow_action.do_release_major(),
+            ReleaseType::Test => common_git_flow_action.do_release_test(),
+            ReleaseType::Hotfix => match (git_flow_parameter.support_branch_name.clone(), git_flow_parameter.base_tag.clone()) {
+                (Some(support_branch_name), _) => common_git_flow_action.do_release_hotfix_on_support(support_branch_name),
+                (None, Some(base_tag)) => common_git_flow_action.do_release_hotfix_from_tag(base_tag),
+                (None, None) => common_git_flow_action.do_release_hotfix(),
+            },
+            ReleaseType::Specific => {
+                common_git_flow_action.do_release_specific(git_flow_parameter.specific_branch_name.clone().unwrap())
+            }
+            ReleaseType::Candidate => common_git_flow_action.do_release_candidate(git_flow_parameter.major),
+            ReleaseType::None => {}
+        },
     }
-    if FlowType::Release.eq(&git_flow_parameter.flow_type) {
-        let selected_release_type = git_flow_parameter.release_type.unwrap();
-        if ReleaseType::Test.eq(&selected_release_type) {
-            common_git_flow_action.do_release_test();
-            return;
-        }
-        if ReleaseType::Hotfix.eq(&selected_release_type) {
-            common_git_flow_action.do_release_hotfix();
-            return;
-        }
-        if ReleaseType::Specific.eq(&selected_release_type) {
-            common_git_flow_action.do_release_specific(git_flow_parameter.specific_branch_name.unwrap());
-            return;
-        }
+    AuditLog::finish(git_flow_parameter.get_git_backend().as_ref());
+    FlowReporter::finish(git_flow_parameter.get_git_backend().as_ref());
+}
+
+fn get_project_type(project_type: Option<ProjectType>) -> ProjectType {
+    let result = match project_type {
+        Some(project_type) => Ok(project_type),
+        None => get_package_type_from_prompt(),
+    };
+    match result {
+        Ok(choice) => choice,
+        Err(_) => fail(&tr!(InvalidProjectType)),
     }
 }
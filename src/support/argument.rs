@@ -0,0 +1,151 @@
+use std::path::PathBuf;
+
+use clap::builder::BoolishValueParser;
+use clap::{ArgAction, Parser, Subcommand};
+
+use crate::support::enums::{FlowType, GitBackendType, Language, OutputFormat, ProjectType, ReleaseType, VerificationLevel};
+
+/// Every input can also be given by its `GITFLOW_*` environment variable, for the pipelines without a terminal
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Arguments {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Git backend used to run git operations : Cli, Libgit2
+    #[arg(long, global = true, default_value = "Cli", env = "GITFLOW_GIT_BACKEND")]
+    pub git_backend: GitBackendType,
+
+    /// Project type, prompted when absent : Maven, Webpack, Helm
+    #[arg(long, global = true, env = "GITFLOW_PROJECT_TYPE")]
+    pub project_type: Option<ProjectType>,
+
+    /// Flow type, prompted when absent : Feature, Hotfix, Release, Support
+    #[arg(long, env = "GITFLOW_FLOW_TYPE")]
+    pub flow_type: Option<FlowType>,
+
+    /// Release type of the Release flow, prompted when absent : Hotfix, Test, Specific, Candidate
+    #[arg(long, env = "GITFLOW_RELEASE_TYPE")]
+    pub release_type: Option<ReleaseType>,
+
+    /// Branch to release by the Specific release, prompted when absent
+    #[arg(long, env = "GITFLOW_SPECIFIC_BRANCH")]
+    pub specific_branch: Option<String>,
+
+    /// Verification of the merged master before the release commit, `release.verification` of the config when absent : Skip, Build, Test
+    #[arg(long, env = "GITFLOW_VERIFICATION")]
+    pub verification: Option<VerificationLevel>,
+
+    /// Hotfix the minor line of an earlier release tag for the Hotfix flow and the Hotfix release,
+    /// without merging into master or develop, or the release tag of the Support flow,
+    /// the tag is selected from a list when no value is given
+    #[arg(long, num_args = 0..=1, default_missing_value = "", env = "GITFLOW_FROM_TAG")]
+    pub from_tag: Option<String>,
+
+    /// Hotfix on the support branch for the Hotfix flow and the Hotfix release,
+    /// the branch is selected from a list when no value is given
+    #[arg(long, num_args = 0..=1, default_missing_value = "", env = "GITFLOW_SUPPORT_BRANCH")]
+    pub support_branch: Option<String>,
+
+    /// Start the feature of the next major version by the Feature flow, or release it by the Test and Candidate releases
+    #[arg(long, env = "GITFLOW_MAJOR", value_parser = BoolishValueParser::new())]
+    pub major: bool,
+
+    /// Output of the flow : Text, Json prints a report of the flow on stdout when it finishes, the logs go to stderr
+    #[arg(long, default_value = "Text", env = "GITFLOW_OUTPUT")]
+    pub output: OutputFormat,
+
+    /// Language of messages, detected from LC_ALL, LC_MESSAGES or LANG when absent : Zh, En
+    #[arg(long, global = true, env = "GITFLOW_LANG")]
+    pub lang: Option<Language>,
+
+    /// Only print warnings and errors, `-qq` only prints errors
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "verbose")]
+    pub quiet: u8,
+
+    /// Print the commands executed with their durations and outputs, `-vv` prints everything
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Append every message and the full output of commands with timestamps to the file, whatever the verbosity
+    #[arg(long, global = true, env = "GITFLOW_LOG_FILE")]
+    pub log_file: Option<PathBuf>,
+
+    /// Execute without the confirmation prompt, select all branches for cleanup,
+    /// required when stdin is not a terminal as nothing can be prompted
+    #[arg(short, long, global = true, env = "GITFLOW_YES", value_parser = BoolishValueParser::new())]
+    pub yes: bool,
+}
+
+impl Arguments {
+    /// The inputs which would be prompted, by their flags and environment variables
+    pub fn missing_inputs(&self) -> Vec<&'static str> {
+        let mut missing_inputs = Vec::new();
+        let needs_project_type = match &self.command {
+            None => true,
+            Some(command) => matches!(command, Command::Status | Command::VerifyVersions),
+        };
+        if needs_project_type && self.project_type.is_none() {
+            missing_inputs.push("--project-type (GITFLOW_PROJECT_TYPE)");
+        }
+        match &self.command {
+            Some(Command::Cleanup { dry_run }) if !dry_run && !self.yes => missing_inputs.push("--yes (GITFLOW_YES)"),
+            Some(Command::Undo) if !self.yes => missing_inputs.push("--yes (GITFLOW_YES)"),
+            Some(_) => {}
+            None => missing_inputs.extend(self.missing_flow_inputs()),
+        }
+        missing_inputs
+    }
+
+    fn missing_flow_inputs(&self) -> Vec<&'static str> {
+        let mut missing_inputs = Vec::new();
+        match (&self.flow_type, &self.release_type) {
+            (None, _) => missing_inputs.push("--flow-type (GITFLOW_FLOW_TYPE)"),
+            (Some(FlowType::Release), None) => missing_inputs.push("--release-type (GITFLOW_RELEASE_TYPE)"),
+            (Some(FlowType::Release), Some(ReleaseType::Specific)) if self.specific_branch.is_none() => {
+                missing_inputs.push("--specific-branch (GITFLOW_SPECIFIC_BRANCH)")
+            }
+            _ => {}
+        }
+        // an empty branch or tag is selected from a list
+        let is_hotfix = self.flow_type == Some(FlowType::Hotfix) || self.release_type == Some(ReleaseType::Hotfix);
+        let is_support = self.flow_type == Some(FlowType::Support);
+        if is_hotfix && self.support_branch.as_deref() == Some("") {
+            missing_inputs.push("--support-branch (GITFLOW_SUPPORT_BRANCH)");
+        } else if (is_hotfix && self.from_tag.as_deref() == Some("")) || (is_support && self.from_tag.as_deref().unwrap_or_default().is_empty()) {
+            missing_inputs.push("--from-tag (GITFLOW_FROM_TAG)");
+        }
+        if !self.yes {
+            missing_inputs.push("--yes (GITFLOW_YES)");
+        }
+        missing_inputs
+    }
+}
+
+/// Commands run instead of a flow
+#[derive(Subcommand)]
+pub enum Command {
+    /// Show current branch, versions, flow branches and the next version of each flow
+    Status,
+
+    /// Delete flow branches merged into master or already released, locally and on origin
+    Cleanup {
+        /// Only print the branches and commands which would be deleted
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Print the audit log of flow executions, the latest first
+    History {
+        /// Only print the latest executions
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+
+    /// Restore the branches and tags changed by the last flow execution, refused if they are pushed or moved since
+    Undo,
+
+    /// Cross-check the versions of branch names, project files, the latest release tag, master and develop,
+    /// print each inconsistency with the command fixing it
+    VerifyVersions,
+}
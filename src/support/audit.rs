@@ -0,0 +1,248 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use chrono::Local;
+use log::warn;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::execution::git_backend::GitBackend;
+use crate::support::parameter::{git_backend_of, GitFlowParameter};
+use crate::tr;
+
+/// The audit log is appended under the git directory, one JSON record per line
+pub const AUDIT_LOG_FILE_NAME: &str = "gitflow-audit.jsonl";
+
+/// The exit code in the error of cmd_lib, `... exited with error; status code: 128 at ...`
+static EXIT_CODE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"status code: (-?\d+)").unwrap());
+
+/// The record of the flow in execution and the file it is appended to
+static CURRENT_RECORD: Mutex<Option<(AuditRecord, PathBuf)>> = Mutex::new(None);
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct CommandRecord {
+    pub command: String,
+    pub exit_code: Option<i32>,
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Success,
+    Failure,
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub user: String,
+    pub time: String,
+    pub parameters: GitFlowParameter,
+    pub original_branch: Option<String>,
+    pub commands: Vec<CommandRecord>,
+    pub refs_before: BTreeMap<String, String>,
+    pub refs_after: BTreeMap<String, String>,
+    pub outcome: Outcome,
+    pub message: Option<String>,
+    /// The time of the execution undone by this record, none for the flows
+    #[serde(default)]
+    pub undo_of: Option<String>,
+}
+
+/// A ref created, moved or deleted by a flow
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct RefChange {
+    pub name: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl AuditRecord {
+    /// The refs which are different after the flow, in the order of their names
+    pub fn ref_changes(&self) -> Vec<RefChange> {
+        ref_changes_between(&self.refs_before, &self.refs_after)
+    }
+}
+
+/// The refs which are different in the two listings of refs, in the order of their names
+pub fn ref_changes_between(refs_before: &BTreeMap<String, String>, refs_after: &BTreeMap<String, String>) -> Vec<RefChange> {
+    refs_before.keys()
+        .chain(refs_after.keys())
+        .collect::<BTreeSet<&String>>()
+        .into_iter()
+        .filter_map(|name| {
+            let before = refs_before.get(name);
+            let after = refs_after.get(name);
+            if before == after {
+                return None;
+            }
+            Some(RefChange {
+                name: name.to_string(),
+                before: before.cloned(),
+                after: after.cloned(),
+            })
+        })
+        .collect::<Vec<RefChange>>()
+}
+
+pub struct AuditLog {}
+
+impl AuditLog {
+    /// Starts recording a flow, nothing is recorded if the git directory is unknown
+    pub fn start(parameters: &GitFlowParameter, git_backend: &dyn GitBackend) {
+        Self::start_record(parameters, None, git_backend);
+    }
+
+    /// Starts recording the undo of the execution, which is not undone again once the undo succeeds
+    pub fn start_undo(undone: &AuditRecord, git_backend: &dyn GitBackend) {
+        Self::start_record(&undone.parameters, Some(undone.time.clone()), git_backend);
+    }
+
+    fn start_record(parameters: &GitFlowParameter, undo_of: Option<String>, git_backend: &dyn GitBackend) {
+        let Some(git_dir) = git_backend.get_git_dir() else {
+            return;
+        };
+        let record = AuditRecord {
+            user: git_backend.get_user_name()
+                .or_else(|| env::var("USER").ok())
+                .or_else(|| env::var("USERNAME").ok())
+                .unwrap_or_default(),
+            time: Local::now().to_rfc3339(),
+            parameters: parameters.clone(),
+            original_branch: git_backend.get_current_branch_name(),
+            commands: Vec::new(),
+            refs_before: git_backend.list_refs(),
+            refs_after: BTreeMap::new(),
+            outcome: Outcome::Success,
+            message: None,
+            undo_of,
+        };
+        *CURRENT_RECORD.lock().unwrap() = Some((record, git_dir.join(AUDIT_LOG_FILE_NAME)));
+    }
+
+    /// Records a command executed by the flow in recording, `None` exit code if it did not run
+    pub fn record_command(command: String, exit_code: Option<i32>) {
+        if let Some((record, _)) = CURRENT_RECORD.lock().unwrap().as_mut() {
+            record.commands.push(CommandRecord { command, exit_code });
+        }
+    }
+
+    /// Records the refs after the flow and appends the record to the audit log
+    pub fn finish(git_backend: &dyn GitBackend) {
+        // the record is taken first so the commands listing refs are not recorded
+        let current_record = CURRENT_RECORD.lock().unwrap().take();
+        if let Some((mut record, log_path)) = current_record {
+            record.refs_after = git_backend.list_refs();
+            Self::append(&record, &log_path);
+        }
+    }
+
+    /// Records the failure of the flow in recording and appends the record to the audit log
+    pub fn finish_with_failure(message: &str) {
+        let current_record = CURRENT_RECORD.lock().unwrap().take();
+        if let Some((mut record, log_path)) = current_record {
+            record.refs_after = git_backend_of(&record.parameters.git_backend_type).list_refs();
+            record.outcome = Outcome::Failure;
+            record.message = Some(String::from(message));
+            Self::append(&record, &log_path);
+        }
+    }
+
+    /// Reads all records of the audit log, the lines which can not be parsed are skipped
+    pub fn read_all(git_backend: &dyn GitBackend) -> Vec<AuditRecord> {
+        let Some(git_dir) = git_backend.get_git_dir() else {
+            return Vec::new();
+        };
+        fs::read_to_string(git_dir.join(AUDIT_LOG_FILE_NAME))
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str::<AuditRecord>(line).ok())
+            .collect::<Vec<AuditRecord>>()
+    }
+
+    /// The exit code of a command run by cmd_lib
+    pub fn exit_code_of<T>(result: &io::Result<T>) -> Option<i32> {
+        match result {
+            Ok(_) => Some(0),
+            Err(err) => EXIT_CODE_REGEX
+                .captures(&err.to_string())
+                .and_then(|captures| captures[1].parse::<i32>().ok()),
+        }
+    }
+
+    fn append(record: &AuditRecord, log_path: &Path) {
+        let result = serde_json::to_string(record)
+            .map_err(io::Error::other)
+            .and_then(|line| {
+                let mut file = OpenOptions::new().create(true).append(true).open(log_path)?;
+                writeln!(file, "{}", line)
+            });
+        if let Err(err) = result {
+            warn!("{}", tr!(WriteAuditLogFailed, log_path.display(), err));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::io;
+
+    use crate::support::audit::{AuditLog, AuditRecord, Outcome, RefChange};
+    use crate::support::enums::{FlowType, ProjectType};
+    use crate::support::parameter::GitFlowParameter;
+
+    #[test]
+    fn ref_changes() {
+        let record = AuditRecord {
+            user: String::from("someone"),
+            time: String::from("2024-01-01T00:00:00+00:00"),
+            parameters: GitFlowParameter::new(ProjectType::Maven, FlowType::Feature),
+            original_branch: Some(String::from("develop")),
+            commands: Vec::new(),
+            refs_before: BTreeMap::from([
+                (String::from("refs/heads/develop"), String::from("a")),
+                (String::from("refs/heads/master"), String::from("b")),
+                (String::from("refs/heads/hotfix/1.2.1"), String::from("c")),
+            ]),
+            refs_after: BTreeMap::from([
+                (String::from("refs/heads/develop"), String::from("d")),
+                (String::from("refs/heads/master"), String::from("b")),
+                (String::from("refs/heads/feature/1.3.0"), String::from("d")),
+            ]),
+            outcome: Outcome::Success,
+            message: None,
+            undo_of: None,
+        };
+        let line = serde_json::to_string(&record).unwrap();
+        let parsed = serde_json::from_str::<AuditRecord>(&line).unwrap();
+        assert_eq!(
+            vec![
+                RefChange { name: String::from("refs/heads/develop"), before: Some(String::from("a")), after: Some(String::from("d")) },
+                RefChange { name: String::from("refs/heads/feature/1.3.0"), before: None, after: Some(String::from("d")) },
+                RefChange { name: String::from("refs/heads/hotfix/1.2.1"), before: Some(String::from("c")), after: None },
+            ],
+            parsed.ref_changes()
+        );
+    }
+
+    #[test]
+    fn exit_code_of() {
+        assert_eq!(Some(0), AuditLog::exit_code_of(&Ok(())));
+        let error = io::Error::other("Running [\"git\" \"push\"] exited with error; status code: 128 at src/main.rs:1");
+        assert_eq!(Some(128), AuditLog::exit_code_of::<()>(&Err(error)));
+        assert_eq!(None, AuditLog::exit_code_of::<()>(&Err(io::Error::other("No such file"))));
+    }
+}
@@ -0,0 +1,249 @@
+use std::fs;
+use std::io::ErrorKind;
+
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::support::enums::{HookPoint, VerificationLevel, VersionScheme};
+use crate::support::failure::fail;
+use crate::support::replacer::VersionReplacer;
+use crate::support::version::{CalendarPattern, TagFormat, VersionUtil};
+use crate::tr;
+
+/// The config file read from the working directory, every entry is optional
+pub const CONFIG_FILE_NAME: &str = ".gitflow.toml";
+
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Clone)]
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct GitFlowConfig {
+    pub hooks: HookConfig,
+    pub release: ReleaseConfig,
+    /// The other files updated with the project version
+    pub version_replacers: Vec<VersionReplacer>,
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ReleaseConfig {
+    /// How the merged master is verified before the release commit
+    pub verification: VerificationLevel,
+    /// How the versions of releases and hotfixes are numbered
+    pub version_scheme: VersionScheme,
+    /// The pattern of calendar versions, only used by the CalVer scheme
+    pub calendar_pattern: CalendarPattern,
+    /// The pre-release identifier of release candidates, `RC` for `1.3.0-RC.1`
+    pub candidate_identifier: String,
+    /// The format of release tag names, `v{version}.RELEASE.{date}` for `v1.3.0.RELEASE.20230101`
+    pub tag_format: TagFormat,
+}
+
+impl Default for ReleaseConfig {
+    fn default() -> ReleaseConfig {
+        ReleaseConfig {
+            verification: VerificationLevel::default(),
+            version_scheme: VersionScheme::default(),
+            calendar_pattern: CalendarPattern::default(),
+            candidate_identifier: String::from("RC"),
+            tag_format: TagFormat::default(),
+        }
+    }
+}
+
+impl ReleaseConfig {
+    /// The version to release by the test flow after the last release
+    pub fn next_release_version_number(&self, last_tag_name: Option<String>) -> String {
+        match self.version_scheme {
+            VersionScheme::SemVer => VersionUtil::next_release_test_version_number(last_tag_name),
+            VersionScheme::CalVer => self.next_calendar_version_number(
+                last_tag_name.and_then(|tag_name| VersionUtil::version_number_of_tag(&tag_name))
+            ),
+        }
+    }
+
+    /// The version to release by the test flow, for CalVer the version of the test branch released after the last release,
+    /// as the period may be over since the test branch is created
+    pub fn next_release_version_number_of_test_branches(&self, last_tag_name: Option<String>, test_version_numbers: &[String]) -> String {
+        if VersionScheme::CalVer == self.version_scheme {
+            let last_version_number = last_tag_name.as_ref().and_then(|tag_name| VersionUtil::version_number_of_tag(tag_name));
+            let option_test_version_number = self.calendar_pattern.first_release_after(test_version_numbers, last_version_number.as_deref());
+            if let Some(test_version_number) = option_test_version_number {
+                return test_version_number;
+            }
+        }
+        self.next_release_version_number(last_tag_name)
+    }
+
+    /// The version of hotfix after the last release, none if nothing is released yet
+    pub fn next_hotfix_version_number(&self, last_tag_name: Option<String>) -> Option<String> {
+        match self.version_scheme {
+            VersionScheme::SemVer => VersionUtil::next_hotfix_version_number(last_tag_name),
+            VersionScheme::CalVer => last_tag_name
+                .and_then(|tag_name| VersionUtil::version_number_of_tag(&tag_name))
+                .and_then(|version_number| self.calendar_pattern.next_hotfix_version_number(&version_number)),
+        }
+    }
+
+    /// The line of releases the version number is hotfixed in, `1.3` of `1.3.4` or `2026.10.0` of the CalVer `2026.10.0.1`
+    pub fn hotfix_line_of(&self, version_number: &str) -> Option<String> {
+        match self.version_scheme {
+            VersionScheme::SemVer => VersionUtil::minor_line_of(version_number),
+            VersionScheme::CalVer => self.calendar_pattern.release_version_number_of(version_number)
+                .filter(|line| VersionUtil::is_version_number(line)),
+        }
+    }
+
+    /// The next hotfix of the line after the releases of the line tagged
+    pub fn next_hotfix_version_number_in_line(&self, line: &str, tag_names: &[String]) -> Option<String> {
+        match self.version_scheme {
+            VersionScheme::SemVer => VersionUtil::next_patch_in_line(&(line.to_owned() + ".0"), tag_names),
+            VersionScheme::CalVer => self.calendar_pattern.next_hotfix_in_line(line, tag_names),
+        }
+    }
+
+    /// The version number master and develop move to after the release
+    pub fn next_development_version_number(&self, released_version_number: &str) -> Option<String> {
+        match self.version_scheme {
+            VersionScheme::SemVer => VersionUtil::next_minor(released_version_number),
+            VersionScheme::CalVer => Some(self.next_calendar_version_number(Some(String::from(released_version_number)))),
+        }
+    }
+
+    fn next_calendar_version_number(&self, last_version_number: Option<String>) -> String {
+        self.calendar_pattern.next_version_number(Utc::now().date_naive(), last_version_number.as_deref())
+    }
+}
+
+/// Shell commands run at each hook point, in order
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Clone)]
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct HookConfig {
+    pub pre_flow: Vec<String>,
+    pub post_version_change: Vec<String>,
+    pub pre_release_commit: Vec<String>,
+    pub post_tag: Vec<String>,
+    pub post_flow: Vec<String>,
+}
+
+impl HookConfig {
+    pub fn is_empty(&self) -> bool {
+        [&self.pre_flow, &self.post_version_change, &self.pre_release_commit, &self.post_tag, &self.post_flow]
+            .iter()
+            .all(|commands| commands.is_empty())
+    }
+
+    pub fn commands_of(&self, hook_point: &HookPoint) -> &[String] {
+        match hook_point {
+            HookPoint::PreFlow => &self.pre_flow,
+            HookPoint::PostVersionChange => &self.post_version_change,
+            HookPoint::PreReleaseCommit => &self.pre_release_commit,
+            HookPoint::PostTag => &self.post_tag,
+            HookPoint::PostFlow => &self.post_flow,
+        }
+    }
+}
+
+impl GitFlowConfig {
+    /// Loads the config file, the default config if there is none
+    pub fn load() -> GitFlowConfig {
+        let content = match fs::read_to_string(CONFIG_FILE_NAME) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return GitFlowConfig::default(),
+            Err(err) => fail(&tr!(ReadConfigFailed, CONFIG_FILE_NAME, err)),
+        };
+        match Self::parse(&content) {
+            Ok(config) => config,
+            Err(err) => fail(&tr!(ReadConfigFailed, CONFIG_FILE_NAME, err)),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<GitFlowConfig, toml::de::Error> {
+        toml::from_str::<GitFlowConfig>(content)
+    }
+
+    /// Checks every version replacer before the flow changes anything
+    pub fn validate_version_replacers(&self) -> Result<(), String> {
+        self.version_replacers.iter().try_for_each(VersionReplacer::validate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::support::config::GitFlowConfig;
+    use crate::support::enums::{HookPoint, VerificationLevel, VersionScheme};
+
+    #[test]
+    fn parse_hooks() {
+        let config = GitFlowConfig::parse(r#"
+            [hooks]
+            pre-release-commit = ["./check-license-header.sh"]
+            post-version-change = ["npm run generate-client", "git add ./generated"]
+        "#).unwrap();
+        assert_eq!(vec!["./check-license-header.sh"], config.hooks.commands_of(&HookPoint::PreReleaseCommit));
+        assert_eq!(2, config.hooks.commands_of(&HookPoint::PostVersionChange).len());
+        assert!(config.hooks.commands_of(&HookPoint::PostTag).is_empty());
+        assert!(GitFlowConfig::parse("").unwrap().hooks.pre_flow.is_empty());
+        assert!(GitFlowConfig::parse("[hooks]\npre-commit = [\"true\"]").is_err());
+    }
+
+    #[test]
+    fn parse_version_replacers() {
+        let config = GitFlowConfig::parse(r#"
+            [[version-replacers]]
+            file = "Dockerfile"
+            pattern = 'LABEL version="([^"]+)"'
+
+            [[version-replacers]]
+            file = "src/**/version.ts"
+            pattern = "VERSION = '(?P<version>[^']+)'"
+        "#).unwrap();
+        assert_eq!(2, config.version_replacers.len());
+        assert_eq!("src/**/version.ts", config.version_replacers[1].file);
+        assert!(GitFlowConfig::parse("[[version-replacers]]\nfile = \"Dockerfile\"").is_err());
+    }
+
+    #[test]
+    fn parse_verification_level() {
+        assert_eq!(VerificationLevel::Build, GitFlowConfig::parse("").unwrap().release.verification);
+        let config = GitFlowConfig::parse("[release]\nverification = \"test\"").unwrap();
+        assert_eq!(VerificationLevel::Test, config.release.verification);
+        assert!(GitFlowConfig::parse("[release]\nverification = \"lint\"").is_err());
+    }
+
+    #[test]
+    fn parse_version_scheme() {
+        let config = GitFlowConfig::parse("").unwrap();
+        assert_eq!(VersionScheme::SemVer, config.release.version_scheme);
+        assert_eq!(String::from("1.3.0"), config.release.next_release_version_number(Some(String::from("v1.2.0.RELEASE.20240101"))));
+        let config = GitFlowConfig::parse("[release]\nversion-scheme = \"calver\"\ncalendar-pattern = \"YYYY.0M.SEQ\"").unwrap();
+        assert_eq!(VersionScheme::CalVer, config.release.version_scheme);
+        assert_eq!(None, config.release.next_hotfix_version_number(None));
+        let last_tag_name = Some(String::from("v2026.09.0.RELEASE.20260930"));
+        assert_eq!(Some(String::from("2026.09.0.1")), config.release.next_hotfix_version_number(last_tag_name.clone()));
+        let test_version_numbers = vec![String::from("2026.09.1")];
+        assert_eq!("2026.09.1", config.release.next_release_version_number_of_test_branches(last_tag_name, &test_version_numbers));
+        assert!(GitFlowConfig::parse("[release]\ncalendar-pattern = \"YYYY.MM\"").is_err());
+    }
+
+    #[test]
+    fn parse_tag_format() {
+        let config = GitFlowConfig::parse("[release]\ntag-format = \"{version}\"").unwrap();
+        assert_eq!(Some(String::from("1.3.0")), config.release.tag_format.version_number_of("1.3.0"));
+        assert!(GitFlowConfig::parse("[release]\ntag-format = \"v{date}\"").is_err());
+    }
+
+    #[test]
+    fn parse_candidate_identifier() {
+        assert_eq!("RC", GitFlowConfig::parse("").unwrap().release.candidate_identifier);
+        assert_eq!("RC", GitFlowConfig::parse("[release]\nverification = \"skip\"").unwrap().release.candidate_identifier);
+        let config = GitFlowConfig::parse("[release]\ncandidate-identifier = \"rc\"").unwrap();
+        assert_eq!("rc", config.release.candidate_identifier);
+    }
+}
@@ -2,14 +2,21 @@ use std::fmt;
 use std::str::FromStr;
 
 use inquire::{CustomUserError, InquireError};
+use serde::{Deserialize, Serialize};
+
+use crate::tr;
 
 #[derive(Debug)]
 #[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
 pub enum ProjectType {
     // Maven project
     Maven,
     //Webpack project
     Webpack,
+    //Helm chart
+    Helm,
 }
 
 impl FromStr for ProjectType {
@@ -19,9 +26,8 @@ impl FromStr for ProjectType {
         match input {
             "Maven" => Ok(ProjectType::Maven),
             "Webpack" => Ok(ProjectType::Webpack),
-            _ => Err(InquireError::Custom(CustomUserError::from(
-                "ProjectType not found",
-            ))),
+            "Helm" => Ok(ProjectType::Helm),
+            _ => Err(InquireError::Custom(CustomUserError::from(tr!(NotFound, "ProjectType")))),
         }
     }
 }
@@ -34,6 +40,8 @@ impl fmt::Display for ProjectType {
 
 #[derive(Debug)]
 #[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
 pub enum FlowType {
     //The Feature flow
     Feature,
@@ -43,6 +51,9 @@ pub enum FlowType {
 
     //The Release flow
     Release,
+
+    //The Support flow, a maintenance line from a release tag
+    Support,
 }
 
 impl FromStr for FlowType {
@@ -53,9 +64,8 @@ impl FromStr for FlowType {
             "Feature" => Ok(FlowType::Feature),
             "Hotfix" => Ok(FlowType::Hotfix),
             "Release" => Ok(FlowType::Release),
-            _ => Err(InquireError::Custom(CustomUserError::from(
-                "FlowType not found",
-            ))),
+            "Support" => Ok(FlowType::Support),
+            _ => Err(InquireError::Custom(CustomUserError::from(tr!(NotFound, "FlowType")))),
         }
     }
 }
@@ -68,6 +78,8 @@ impl fmt::Display for FlowType {
 
 #[derive(Debug)]
 #[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
 pub enum ReleaseType {
     // none
     None,
@@ -80,6 +92,9 @@ pub enum ReleaseType {
 
     //Release from a Specific branch
     Specific,
+
+    //Tag a release candidate on the Test branch
+    Candidate,
 }
 
 impl FromStr for ReleaseType {
@@ -90,9 +105,8 @@ impl FromStr for ReleaseType {
             "Hotfix" => Ok(ReleaseType::Hotfix),
             "Test" => Ok(ReleaseType::Test),
             "Specific" => Ok(ReleaseType::Specific),
-            _ => Err(InquireError::Custom(CustomUserError::from(
-                "ReleaseType not found",
-            ))),
+            "Candidate" => Ok(ReleaseType::Candidate),
+            _ => Err(InquireError::Custom(CustomUserError::from(tr!(NotFound, "ReleaseType")))),
         }
     }
 }
@@ -102,3 +116,207 @@ impl fmt::Display for ReleaseType {
         fmt::Debug::fmt(self, f)
     }
 }
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
+pub enum GitBackendType {
+    //Run the local git command
+    Cli,
+
+    //Run git operations in process through libgit2
+    Libgit2,
+}
+
+impl FromStr for GitBackendType {
+    type Err = InquireError;
+
+    fn from_str(input: &str) -> Result<GitBackendType, InquireError> {
+        match input {
+            "Cli" => Ok(GitBackendType::Cli),
+            "Libgit2" => Ok(GitBackendType::Libgit2),
+            _ => Err(InquireError::Custom(CustomUserError::from(tr!(NotFound, "GitBackendType")))),
+        }
+    }
+}
+
+impl fmt::Display for GitBackendType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
+pub enum Language {
+    //Simplified Chinese
+    Zh,
+
+    //English
+    En,
+}
+
+impl FromStr for Language {
+    type Err = InquireError;
+
+    fn from_str(input: &str) -> Result<Language, InquireError> {
+        match input {
+            "Zh" | "zh" => Ok(Language::Zh),
+            "En" | "en" => Ok(Language::En),
+            _ => Err(InquireError::Custom(CustomUserError::from(tr!(NotFound, "Language")))),
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum OutputFormat {
+    //Only the coloured logs
+    Text,
+
+    //A JSON report on stdout when the flow finishes, the logs stay on stderr
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = InquireError;
+
+    fn from_str(input: &str) -> Result<OutputFormat, InquireError> {
+        match input {
+            "Text" | "text" => Ok(OutputFormat::Text),
+            "Json" | "json" => Ok(OutputFormat::Json),
+            _ => Err(InquireError::Custom(CustomUserError::from(tr!(NotFound, "OutputFormat")))),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum HookPoint {
+    //Before the flow changes anything
+    PreFlow,
+
+    //After the version of the project is modified
+    PostVersionChange,
+
+    //Before the release commit
+    PreReleaseCommit,
+
+    //After the release tag is created
+    PostTag,
+
+    //After the flow is completed
+    PostFlow,
+}
+
+impl fmt::Display for HookPoint {
+    /// The name of the hook in the config file, `pre-flow` for `PreFlow`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            HookPoint::PreFlow => "pre-flow",
+            HookPoint::PostVersionChange => "post-version-change",
+            HookPoint::PreReleaseCommit => "pre-release-commit",
+            HookPoint::PostTag => "post-tag",
+            HookPoint::PostFlow => "post-flow",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Default)]
+#[derive(Serialize, Deserialize)]
+pub enum VerificationLevel {
+    //Release without verification
+    #[serde(alias = "skip")]
+    Skip,
+
+    //Build the project
+    #[default]
+    #[serde(alias = "build")]
+    Build,
+
+    //Build the project and run its tests
+    #[serde(alias = "test")]
+    Test,
+}
+
+impl FromStr for VerificationLevel {
+    type Err = InquireError;
+
+    fn from_str(input: &str) -> Result<VerificationLevel, InquireError> {
+        match input {
+            "Skip" | "skip" => Ok(VerificationLevel::Skip),
+            "Build" | "build" => Ok(VerificationLevel::Build),
+            "Test" | "test" => Ok(VerificationLevel::Test),
+            _ => Err(InquireError::Custom(CustomUserError::from(tr!(NotFound, "VerificationLevel")))),
+        }
+    }
+}
+
+impl fmt::Display for VerificationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Default)]
+#[derive(Serialize, Deserialize)]
+pub enum VersionScheme {
+    //Minor for releases and patch for hotfixes, `1.3.0` after `1.2.0`
+    #[default]
+    #[serde(alias = "semver")]
+    SemVer,
+
+    //Date and the release sequence within the period, `2026.10.1` after `2026.10.0`
+    #[serde(alias = "calver")]
+    CalVer,
+}
+
+impl fmt::Display for VersionScheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum PackageManager {
+    //npm, with package-lock.json or without lockfile
+    Npm,
+
+    //yarn, with yarn.lock
+    Yarn,
+
+    //pnpm, with pnpm-lock.yaml
+    Pnpm,
+}
+
+impl fmt::Display for PackageManager {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
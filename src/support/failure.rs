@@ -0,0 +1,14 @@
+use std::process::exit;
+
+use log::error;
+
+use crate::support::audit::AuditLog;
+use crate::support::report::FlowReporter;
+
+/// Prints the error, records the failure into the audit log and the report, then exits the process with code 1
+pub fn fail(message: &str) -> ! {
+    error!("{}", message);
+    AuditLog::finish_with_failure(message);
+    FlowReporter::finish_with_failure(message);
+    exit(1);
+}
@@ -0,0 +1,356 @@
+use std::env;
+use std::fmt::{Display, Write};
+use std::sync::OnceLock;
+
+use crate::support::enums::Language;
+
+/// The language used when no locale is configured
+pub const DEFAULT_LANGUAGE: Language = Language::Zh;
+
+/// The locale environment variables, in the order gettext looks them up
+const LOCALE_ENVS: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+/// Declares [Message] with its Chinese and English templates, `{}` is replaced by the arguments in order
+macro_rules! messages {
+    ($($name:ident => ($zh:expr, $en:expr),)*) => {
+        #[derive(Debug)]
+        #[derive(PartialEq)]
+        #[derive(Clone, Copy)]
+        pub enum Message {
+            $($name,)*
+        }
+
+        impl Message {
+            pub const ALL: &'static [Message] = &[$(Message::$name,)*];
+
+            pub fn template(&self, language: &Language) -> &'static str {
+                match (self, language) {
+                    $(
+                        (Message::$name, Language::Zh) => $zh,
+                        (Message::$name, Language::En) => $en,
+                    )*
+                }
+            }
+        }
+    };
+}
+
+messages! {
+    // main
+    GitNotFound => ("未在本机器识别到git命令", "Command <git> is not found on this machine"),
+    GitVersionTooLow => ("本机git命令，版本过低，最小版本 :{}", "The local git is too old, minimum version : {}"),
+    InvalidProjectType => ("未识别的项目类型，请重试", "Unrecognized project type, please retry"),
+    MissingInputs => ("没有终端无法提示输入，请通过参数或环境变量提供 : {}", "Nothing can be prompted without a terminal, please give the inputs by flags or environment variables : {}"),
+    OpenLogFileFailed => ("无法打开日志文件 : {} , {}", "Can't open log file : {} , {}"),
+    CommandStarted => ("执行命令 : {}", "Run command : {}"),
+    CommandFinished => ("命令结束 : {} , 退出码 : {} , 耗时 : {} ms", "Command finished : {} , Exit code : {} , Duration : {} ms"),
+    InvalidFlowType => ("未识别的流程类型，请重试", "Unrecognized flow type, please retry"),
+    InvalidReleaseType => ("未识别的Release类型，请重试", "Unrecognized release type, please retry"),
+    InvalidSpecificBranch => ("未识别的要指定的合并分支，请重试", "Unrecognized branch to release, please retry"),
+    InvalidSupportBranch => ("未识别的Support分支，请重试", "Unrecognized support branch, please retry"),
+    MajorFlowOnly => ("--major 只能用于Feature流程、Test发布或Candidate发布", "--major only applies to the Feature flow, the Test release or the Candidate release"),
+    InvalidBaseTag => ("未识别的Release Tag，请重试", "Unrecognized release tag, please retry"),
+    Cancelled => ("已取消任务。", "Execution cancelled."),
+    NotFound => ("未找到{}", "{} not found"),
+
+    // prompts
+    PromptProjectType => ("请选择当前项目的类型...", "Select the type of current project..."),
+    PromptFlowType => ("请选择要执行的流程...", "Select the flow to execute..."),
+    PromptReleaseType => ("请选择一个Release类型...", "Select a release type..."),
+    PromptConfirm => ("是否确认执行上述操作?", "Execute the operations above?"),
+    PromptSpecificBranch => ("请选择要特殊Release的分支名称", "Select the branch to release"),
+    PromptBranchesToDelete => ("请选择要删除的分支", "Select the branches to delete"),
+    NoDevelopmentBranch => ("没有处于开发版本的分支", "There is no branch with a development version"),
+    PromptSupportBranch => ("请选择Support分支", "Select the support branch"),
+    NoSupportBranch => ("没有Support分支", "There is no support branch"),
+    PromptReleaseTag => ("请选择基于的Release Tag", "Select the base release tag"),
+    NoReleaseTag => ("没有Release Tag", "There is no release tag"),
+
+    // parameters
+    ParameterProjectType => ("项目类型", "Project type"),
+    ParameterFlowType => ("流程类型", "Flow type"),
+    ParameterReleaseType => ("Release类型", "Release type"),
+    ParameterSpecificBranch => ("指定的Release分支", "Branch to release"),
+    ParameterSupportBranch => ("Support分支", "Support branch"),
+    ParameterMajor => ("主版本", "Major version"),
+    ParameterBaseTag => ("基于的Release Tag", "Base release tag"),
+    ParameterGitBackend => ("Git后端", "Git backend"),
+    ParameterNeedPush => ("是否推送分支到远端", "Push branches to remote"),
+    ParameterNeedPushTag => ("是否推送Tag到远端", "Push tags to remote"),
+
+    // table headers
+    HeaderOption => ("选项", "option"),
+    HeaderValue => ("值", "value"),
+    HeaderBranch => ("分支", "branch"),
+    HeaderLocation => ("位置", "location"),
+    HeaderVersion => ("版本", "version"),
+    HeaderFlow => ("流程", "flow"),
+    HeaderBranchOrTag => ("分支或Tag", "branch_or_tag"),
+    HeaderLocal => ("本地", "local"),
+    HeaderRemote => ("远端", "remote"),
+    HeaderReason => ("原因", "reason"),
+    HeaderFound => ("实际", "found"),
+    HeaderExpected => ("期望", "expected"),
+    HeaderFix => ("修复命令", "fix"),
+
+    // status command
+    StatusCurrentBranch => ("当前分支", "Current branch"),
+    StatusCurrentVersion => ("当前项目版本", "Current project version"),
+    StatusLastTag => ("最新Release Tag", "Latest release tag"),
+    StatusMasterVersion => ("master版本", "master version"),
+    StatusDevelopVersion => ("develop版本", "develop version"),
+    LocationLocal => ("本地", "local"),
+    LocationOrigin => ("origin", "origin"),
+
+    // cleanup command
+    NothingToCleanUp => ("没有已合并或已发布的流程分支需要清理", "There is no merged or released flow branch to clean up"),
+    DryRunDeleteBranch => ("[演练]git branch -D {}", "[Dry run]git branch -D {}"),
+    DryRunDeleteRemoteBranch => ("[演练]git push origin --delete {}", "[Dry run]git push origin --delete {}"),
+    InvalidBranchesToDelete => ("未识别的要删除的分支，请重试", "Unrecognized branches to delete, please retry"),
+    CannotDeleteCurrentBranch => ("当前分支不能被删除，请切换到其他分支 : {}", "Current branch can not be deleted, please switch to another branch : {}"),
+    VersionsConsistent => ("版本一致", "Versions are consistent"),
+    VersionsInconsistent => ("发现 {} 处版本不一致", "{} version inconsistencies found"),
+    TagVersionManualFix => ("需手动修复 : 找到版本为 {} 的发布提交并重新打Tag，git tag --force {} <提交> && git push --force origin tag {}", "Fix manually : find the release commit of {} and tag it again , git tag --force {} <commit> && git push --force origin tag {}"),
+    CleanupCompleted => ("清理执行完成，已删除分支 : {}", "Cleanup execution completed,Deleted branches : {}"),
+    ReasonMergedIntoMaster => ("已合并到master", "merged into master"),
+    ReasonVersionReleased => ("版本已发布", "version already released"),
+
+    // audit log and history command
+    WriteAuditLogFailed => ("写入执行记录失败 : {} , {}", "Can't write audit log : {} , {}"),
+    NoAuditRecord => ("没有任何执行记录", "There is no execution record"),
+    HeaderTime => ("时间", "time"),
+    HeaderUser => ("用户", "user"),
+    HeaderOriginalBranch => ("原分支", "original_branch"),
+    HeaderRefChanges => ("引用变更", "ref_changes"),
+    HeaderOutcome => ("结果", "outcome"),
+    OutcomeSuccess => ("成功", "success"),
+    OutcomeFailure => ("失败 : {}", "failure : {}"),
+
+    // config and hooks
+    ReadConfigFailed => ("读取配置文件失败 : {} , {}", "Can't read config file : {} , {}"),
+    HookRun => ("[HOOK]执行 {} : {}", "[HOOK]Run {} : {}"),
+    HookFailed => ("钩子执行失败，流程已终止 : {} : {} , {}", "Hook failed, the flow is stopped : {} : {} , {}"),
+    ReleaseHookFailed => ("已取消发布并将 {} 恢复到发布前 : {}", "The release is cancelled and {} is reset to before the release : {}"),
+
+    // release verification
+    VerificationSkipped => ("已跳过发布前的项目校验", "Project verification before release is skipped"),
+    VerificationFailed => ("项目校验失败，已取消发布并将 {} 恢复到发布前 : {}", "Project verification failed, the release is cancelled and {} is reset to before the release : {}"),
+    VerificationChangedFiles => ("项目校验修改了已跟踪的文件，这些修改不能进入发布 : {}", "The verification changed tracked files, the changes can't be released : {}"),
+    CommandFailed => ("命令执行失败 : {} , {}", "Command failed : {} , {}"),
+
+    // undo command
+    NothingToUndo => ("没有修改了分支或Tag且未被撤销的执行", "There is no execution which changed branches or tags and is not undone yet"),
+    UndoRefMoved => ("{} 在上次执行后已被修改，无法撤销", "{} has moved since the last execution, it can not be undone"),
+    UndoRefPushed => ("{} 已推送到远端，无法撤销", "{} has been pushed to remote, it can not be undone"),
+    UndoRefused => ("拒绝撤销上次执行 : {} {}", "Refuse to undo the last execution : {} {}"),
+    UndoDirtyWorkingTree => ("当前分支有未提交的变更，请先提交或清除", "There are uncommitted changes in current branch, please commit or discard them first"),
+    UndoCompleted => ("撤销执行完成，已切换回分支 : {}", "Undo execution completed,Switched back to branch : {}"),
+    UndoOnDeletedBranch => ("上次执行开始于分离的HEAD，当前分支 {} 将被删除，请先切换到其他分支", "The execution started on a detached HEAD and current branch {} is deleted by the undo, please switch to another branch first"),
+    UndoCompletedOnCurrentBranch => ("撤销执行完成，上次执行开始于分离的HEAD，仍在分支 : {}", "Undo execution completed,The execution started on a detached HEAD, staying on branch : {}"),
+    UndoFlowName => ("撤销 {}", "Undo {}"),
+    UndoActionDelete => ("删除", "delete"),
+    UndoActionRestore => ("恢复到 {}", "restore to {}"),
+    HeaderRef => ("引用", "ref"),
+    HeaderBefore => ("执行前", "before"),
+    HeaderAfter => ("执行后", "after"),
+    HeaderAction => ("操作", "action"),
+
+    // git
+    GetGitVersionError => ("获取git版本失败 : {}", "Get git version error : {}"),
+    GetGitStatusError => ("获取git状态失败 : {}", "Get git status error : {}"),
+    CurrentGitVersion => ("当前git版本 : {}", "Current git version : {}"),
+    GitFetch => ("[GIT]从远端拉取", "[GIT]Fetch from remote"),
+    GitPull => ("[GIT]从远端拉取并合并", "[GIT]Pull from remote"),
+    GitSwitchBranch => ("[GIT]切换到分支 : {}", "[GIT]Switch to branch : {}"),
+    GitCheckoutBranch => ("[GIT]检出分支 : {}", "[GIT]Checkout branch : {}"),
+    GitCheckChanges => ("[GIT]检查当前分支是否有变更", "[GIT]Check whether there are anything changed in current branch"),
+    GitCreateBranch => ("[GIT]创建新分支 : {}", "[GIT]Create a new branch : {}"),
+    GitCreateTrackingBranch => ("[GIT]创建跟踪远端的本地分支 : {}", "[GIT]Create local branch tracking remote : {}"),
+    GitMergeToCurrent => ("[GIT]合并分支到当前分支，分支名称 : {}", "[GIT]Merge branch to current,Branch name : {}"),
+    GitCommit => ("[GIT]提交变更", "[GIT]Commit with message"),
+    GitCreateTag => ("[GIT]创建新Tag : {}", "[GIT]Create new tag : {}"),
+    GitGetLastTag => ("[GIT]获取最新Tag名称", "[GIT]Get latest tag name"),
+    GitCheckLocalBranchExists => ("[GIT]检查本地分支是否存在 : {}", "[GIT]Check if local branch exists : {}"),
+    GitCheckRemoteBranchExists => ("[GIT]检查远端分支是否存在 : {}", "[GIT]Check if remote branch exists : {}"),
+    GitDeleteBranch => ("[GIT]删除本地分支 : {}", "[GIT]Delete local branch : {}"),
+    GitDeleteRemoteBranch => ("[GIT]删除远端分支 : {}", "[GIT]Delete remote branch : {}"),
+    GitForceBranch => ("[GIT]将分支 {} 指向 {}", "[GIT]Point branch {} to {}"),
+    GitDeleteTag => ("[GIT]删除Tag : {}", "[GIT]Delete tag : {}"),
+    GitForceTag => ("[GIT]将Tag {} 指向 {}", "[GIT]Point tag {} to {}"),
+    GitPush => ("[GIT]推送到远端", "[GIT]Push to remote"),
+    GitPushTags => ("[GIT]推送Tag到远端", "[GIT]Push tags to remote"),
+    GitPushNewBranch => ("[GIT]推送新分支到远端 : {}", "[GIT]Push new branch to remote : {}"),
+    NoLocalBranch => ("未获取到任何本地分支", "No local branch found"),
+    HeadNotBranch => ("HEAD未指向任何分支", "HEAD is not pointing to a branch"),
+    InvalidBranchName => ("无效的分支名称 : {}", "Invalid branch name : {}"),
+    MergeConflict => ("检测到合并冲突，请手动解决冲突 : {}", "Merge conflict detected, please resolve conflicts manually : {}"),
+    NoTrackingInformation => ("当前分支没有跟踪信息 : {}", "There is no tracking information for the current branch : {}"),
+    AuthenticationFailed => ("认证失败", "Authentication failed"),
+    PushRejected => ("推送{}被拒绝 : {}", "Push of {} rejected : {}"),
+    GitCommandFailed => ("git命令执行失败 : {} , 退出码 : {}", "Git command failed : {} , Exit code : {}"),
+    GitDirtyWorkingTree => ("工作区有未提交的变更会被覆盖，请先提交或暂存", "There are uncommitted changes the command would overwrite, commit or stash them first"),
+    GitNonFastForward => ("远端分支有本地没有的提交，请先合并远端的提交", "The remote branch has commits the local branch does not have, integrate them first"),
+    GitAuthenticationFailed => ("远端拒绝了认证，请检查凭据或SSH密钥", "The remote refused the authentication, check the credentials or the SSH key"),
+    GitMissingRef => ("分支、Tag或提交不存在，可能需要先从远端拉取", "The branch, tag or revision does not exist, it may need to be fetched first"),
+    GitMergeConflict => ("合并出现冲突，请解决冲突后提交，或者放弃合并", "The merge stopped at conflicts, resolve them and commit, or abort the merge"),
+    GitSuggestedFix => ("建议执行 : {}", "Suggested fix : {}"),
+
+    // maven
+    MavenUpdateVersion => ("[MAVEN]更新项目版本为新版本 : {}", "[MAVEN]Update project's version to new version : {}"),
+    MavenCheckPresent => ("[Verify]检查maven是否存在 ...", "[Verify]Check whether maven is present ..."),
+    MavenNotFound => ("命令<mvn>不存在", "Command <mvn> does not exist"),
+    MavenVersionInfo => ("当前maven版本信息 : \n {}", "Current maven version info : \n {}"),
+    MavenVerify => ("[MAVEN]校验当前maven项目", "[MAVEN]Verify current maven project"),
+    MavenUpdateProperty => ("[MAVEN]更新属性版本，属性名称 : {} , 新属性值 : {}", "[MAVEN]Update property's version ,property's name : {} , new property value : {}"),
+    MavenGetVersion => ("[MAVEN]获取当前项目版本", "[MAVEN]Get current project's version"),
+
+    // webpack
+    WebpackVerify => ("[Verify]校验Webpack项目 ...", "[Verify]Verify Webpack project ..."),
+    WebpackGetVersion => ("[Webpack]获取当前项目版本", "[Webpack]Get current project's version"),
+    WebpackUpdateVersion => ("[Webpack]更新项目版本为新版本 : {}", "[Webpack]Update project's version to new version : {}"),
+    PackageJsonNotFound => ("当前目录下没有package.json文件，请检查工作目录", "Current directory does not has a package.json file,please check work dir"),
+    WriteFileFailed => ("写入文件失败 : {} , {}", "Can't write file : {} , {}"),
+    ReadFileFailed => ("读取文件失败 : {} , {}", "Can't read file : {} , {}"),
+    ReplacerUpdateVersion => ("更新文件版本 : {} , 新版本 : {}", "Update version of file : {} , New version : {}"),
+    ReplacerMatchesNothing => ("版本替换没有匹配到任何内容 , 文件 : {} , 正则 : {}", "Version replacer matches nothing , File : {} , Pattern : {}"),
+    InvalidReplacerPattern => ("版本替换的正则无效 : {} , {}", "Invalid pattern of version replacer : {} , {}"),
+    ReplacerNoCapture => ("缺少版本的捕获组", "no capture group of the version"),
+    InvalidReplacerFile => ("版本替换的文件匹配无效 : {} , {}", "Invalid file glob of version replacer : {} , {}"),
+    ParsePackageJsonFailed => ("解析package.json失败 : {}", "Can't parse package.json : {}"),
+    HelmVerify => ("[Verify]检查Helm Chart结构 ...", "[Verify]Lint Helm chart structure ..."),
+    HelmGetVersion => ("[Helm]获取当前Chart版本", "[Helm]Get current chart's version"),
+    HelmUpdateVersion => ("[Helm]更新Chart版本为新版本 : {}", "[Helm]Update chart's version to new version : {}"),
+    ChartYamlNotFound => ("没有找到Chart文件 : {}，请检查工作目录", "Chart file not found : {} ,please check work dir"),
+    ChartFieldMissing => ("Chart文件缺少字段 : {} , 字段 : {}", "Chart file misses field : {} , Field : {}"),
+    ChartApiVersionInvalid => ("Chart文件的apiVersion无效 : {} , apiVersion : {}", "Invalid apiVersion of chart file : {} , apiVersion : {}"),
+    ChartVersionInvalid => ("Chart文件的版本不是SemVer 2版本 : {} , 版本 : {}", "The version of chart file is not a SemVer 2 version : {} , Version : {}"),
+    ParseYamlFailed => ("解析YAML失败 : {} , {}", "Can't parse YAML : {} , {}"),
+    ScriptNotFound => ("package.json中没有 {} 脚本", "There is no {} script in package.json"),
+    TestScriptNotFound => ("package.json中没有 {} 脚本，跳过测试", "There is no {} script in package.json, tests are skipped"),
+    NodeCheckPresent => ("[Verify]检查{}是否存在 ...", "[Verify]Check whether {} is present ..."),
+    NodeVersionInfo => ("当前{}版本 : {}", "Current {} version : {}"),
+    NodeCommandNotFound => ("命令<{}>不存在，请安装后重试", "Command <{}> does not exist, please install it and retry"),
+    NodeCleanInstall => ("[{}]清理安装依赖", "[{}]Clean install dependencies"),
+    NodeRunScript => ("[Webpack]执行脚本 : {}", "[Webpack]Run script : {}"),
+    NodeCommandExited => ("命令 <{}> 执行失败，退出码 : {}，请查看上方输出", "Command <{}> failed with exit code {}, see the output above"),
+    NodeCommandNotRun => ("命令 <{}> 无法执行", "Command <{}> could not be run"),
+
+    // flows
+    ExtractNewVersionFailed => ("无法提取新版本号 : {}", "Can not extract new version number for :{}"),
+    FeatureBranchExists => ("Feature分支已存在，分支名称 : {}", "Feature branch exists ,Branch name : {}"),
+    FeatureFlowCompleted => ("Feature流程执行完成，分支名称 : {}", "Feature flow execution completed,Branch name : {}"),
+    NoTagForHotfix => ("master分支上不存在任何Tag，无法创建Hotfix分支", "There is no tag exists in master branch,Hotfix branch can not be created"),
+    ExtractLatestVersionFailed => ("无法从Tag中提取最新版本号", "Can not extract latest version number from tag"),
+    HotfixBranchExists => ("Hotfix分支已存在，分支名称 : {}", "Hotfix branch exists ,Branch name :{}"),
+    HotfixFlowCompleted => ("Hotfix流程执行完成，分支名称 : {}", "Hotfix flow execution completed,Branch name : {}"),
+    MajorNotSupported => ("{} 版本方案没有主版本", "There is no major version in the {} version scheme"),
+    TestBranchNotExists => ("Test分支不存在，分支名称 : {}", "Test branch doesn't exists ,Branch name : {}"),
+    CandidateReleaseCompleted => ("Release Candidate流程执行完成，Tag名称 : {} , Test分支 : {}", "Release candidate flow execution completed,Tag name : {} , Test branch : {}"),
+    CandidatePromoted => ("发布的Release Candidate : {}", "Promoted release candidate : {}"),
+    CandidateOutdated => ("{} 有 {} 个提交在 {} 之后，不在任何Release Candidate中", "{} has {} commits after {} which no release candidate contains"),
+    ReleaseTestCompleted => ("Feature发布流程执行完成，Release Tag名称 : {} , 下一个Feature分支 : {}", "Feature release flow execution completed,Release tag name : {} , Next feature branch : {}"),
+    ReadBranchVersionFailed => ("无法从分支获取当前项目版本 : {}", "Can not get current project's version from branch : {}"),
+    NotSnapshotVersion => ("当前项目版本不是SNAPSHOT版本，版本 : {}", "Current project's version is not a SNAPSHOT version,Version : {}"),
+    NextDevelopmentVersionFailed => ("无法计算 {} 之后的开发版本号", "Can not compute the development version after : {}"),
+    ExtractReleaseVersionFailed => ("无法提取要发布的版本号 : {}", "Can not extract to release version number : {}"),
+    MergeMasterReminder => ("指定分支的发布流程执行完成，请将master合并到其他feature/test分支以保持与master的更新一致", "Release flow of specific branch execution completed,you should merge master into other feature/test branch to keep code as same as mater's updates"),
+    SpecificReleaseCompleted => ("指定分支发布流程执行完成，Release Tag名称 : {}", "Specific release flow execution completed,Release tag name : {}"),
+    NoTagForHotfixRelease => ("master分支上不存在任何Tag，没有上一个Tag无法发布Hotfix", "There is no tag exists in master branch,Hotfix can not release without previous tag"),
+    HotfixBranchNotExists => ("Hotfix分支不存在，分支名称 : {}", "Hotfix branch doesn't exists ,Branch name : {}"),
+    InvalidCalendarPattern => ("日历版本格式无效 : {} , 应由YYYY、YY、0Y、MM、0M、WW、0W、DD、0D以点分隔并以SEQ结尾", "Invalid calendar version pattern : {} , dot separated YYYY, YY, 0Y, MM, 0M, WW, 0W, DD, 0D ended by SEQ"),
+    InvalidTagFormat => ("发布标签格式无效 : {} , 应包含一个{version}并可包含一个{date}", "Invalid release tag format : {} , one {version} and at most one {date} expected"),
+    InvalidReleaseTag => ("不是Release Tag : {}", "Not a release tag : {}"),
+    TagHotfixReleaseCompleted => ("Hotfix发布流程执行完成，未合并到master和develop，Release Tag名称 : {}", "Hotfix release flow execution completed without merging into master and develop,Release tag name : {}"),
+    NotSupportBranch => ("不是Support分支 : {}", "Not a support branch : {}"),
+    SupportBranchExists => ("Support分支已存在，分支名称 : {}", "Support branch exists ,Branch name : {}"),
+    SupportFlowCompleted => ("Support流程执行完成，分支名称 : {} , 基于Tag : {}", "Support flow execution completed,Branch name : {} , From tag : {}"),
+    SupportHotfixReleaseCompleted => ("Hotfix发布流程执行完成，Release Tag名称 : {} , 已合并到 : {}", "Hotfix release flow execution completed,Release tag name : {} , Merged into : {}"),
+    HotfixReleaseCompleted => ("Hotfix发布流程执行完成，Release Tag名称 : {}", "Hotfix release flow execution completed,Release tag name : {}"),
+}
+
+/// Translates the message into the current language
+#[macro_export]
+macro_rules! tr {
+    ($message:ident $(, $argument:expr)* $(,)?) => {
+        $crate::support::i18n::translate(
+            $crate::support::i18n::Message::$message,
+            &[$(&$argument as &dyn std::fmt::Display),*],
+        )
+    };
+}
+
+/// Sets the language, detected from the locale environment variables when absent.
+/// Only the first call takes effect.
+pub fn init_language(language: Option<Language>) {
+    LANGUAGE.get_or_init(|| language.unwrap_or_else(language_of_env));
+}
+
+pub fn language() -> &'static Language {
+    LANGUAGE.get_or_init(language_of_env)
+}
+
+pub fn translate(message: Message, arguments: &[&dyn Display]) -> String {
+    format_template(message.template(language()), arguments)
+}
+
+fn language_of_env() -> Language {
+    LOCALE_ENVS
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .map(|locale| language_of_locale(&locale))
+        .unwrap_or(DEFAULT_LANGUAGE)
+}
+
+/// `zh_CN.UTF-8` to Chinese, any other locale like `en_US.UTF-8` or `C` to English
+fn language_of_locale(locale: &str) -> Language {
+    if locale.to_lowercase().starts_with("zh") {
+        Language::Zh
+    } else {
+        Language::En
+    }
+}
+
+fn format_template(template: &str, arguments: &[&dyn Display]) -> String {
+    let mut parts = template.split("{}");
+    let mut result = String::from(parts.next().unwrap_or(""));
+    let mut arguments = arguments.iter();
+    for part in parts {
+        if let Some(argument) = arguments.next() {
+            write!(result, "{}", argument).ok();
+        }
+        result.push_str(part);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::support::enums::Language;
+    use crate::support::i18n::{format_template, language_of_locale, Message};
+
+    #[test]
+    fn translations_have_same_placeholders() {
+        for message in Message::ALL {
+            let zh = message.template(&Language::Zh);
+            let en = message.template(&Language::En);
+            assert_eq!(zh.matches("{}").count(), en.matches("{}").count(), "{:?}", message);
+            assert!(!zh.is_empty() && !en.is_empty(), "{:?}", message);
+        }
+    }
+
+    #[test]
+    fn format_arguments_in_order() {
+        assert_eq!("Push of a rejected : b", format_template("Push of {} rejected : {}", &[&"a", &"b"]));
+        assert_eq!("1 - ", format_template("{} - {}", &[&1]));
+    }
+
+    #[test]
+    fn language_of_locales() {
+        assert_eq!(Language::Zh, language_of_locale("zh_CN.UTF-8"));
+        assert_eq!(Language::En, language_of_locale("en_US.UTF-8"));
+        assert_eq!(Language::En, language_of_locale("C"));
+    }
+}
@@ -0,0 +1,147 @@
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use chrono::{Local, SecondsFormat};
+use colour::{e_blue_ln, e_dark_yellow_ln, e_green_ln, e_grey_ln, e_red_ln};
+use log::{debug, Level, LevelFilter, Log, Metadata, Record};
+
+use crate::support::audit::AuditLog;
+use crate::tr;
+
+/// The target of the info records which report a completed flow or command, printed in green
+pub const SUCCESS_TARGET: &str = "gitflow::success";
+
+/// cmd_lib logs the output of the commands it runs under this target
+const COMMAND_OUTPUT_TARGET: &str = "cmd_lib";
+
+/// Logs the completion of a flow or command, at info level
+#[macro_export]
+macro_rules! success {
+    ($($argument:tt)*) => {
+        log::info!(target: $crate::support::logger::SUCCESS_TARGET, $($argument)*)
+    };
+}
+
+/// Prints the records of the console level to stderr, and every record with its time into the log file
+pub struct Logger {
+    console_level: LevelFilter,
+    colored: bool,
+    log_file: Option<Mutex<File>>,
+}
+
+impl Logger {
+    /// Installs the logger, the records of cmd_lib go through it as well.
+    /// Only the first call takes effect.
+    pub fn init(console_level: LevelFilter, log_file_path: Option<&Path>) -> Result<(), String> {
+        let open_result = log_file_path
+            .map(|path| OpenOptions::new().create(true).append(true).open(path).map_err(|err| (path, err)))
+            .transpose();
+        let (log_file, open_error) = match open_result {
+            Ok(log_file) => (log_file, None),
+            Err((path, err)) => (None, Some(tr!(OpenLogFileFailed, path.display(), err))),
+        };
+        let max_level = if log_file.is_some() { LevelFilter::Trace } else { console_level };
+        let logger = Logger {
+            console_level,
+            // https://no-color.org, and no escape codes when stderr is piped into a file or another program
+            colored: io::stderr().is_terminal() && env::var("NO_COLOR").map(|value| value.is_empty()).unwrap_or(true),
+            log_file: log_file.map(Mutex::new),
+        };
+        if log::set_boxed_logger(Box::new(logger)).is_ok() {
+            log::set_max_level(max_level);
+        }
+        match open_error {
+            Some(message) => Err(message),
+            None => Ok(()),
+        }
+    }
+
+    /// The console level from the count of `-q` and `-v` flags, info by default
+    pub fn level_of(quiet: u8, verbose: u8) -> LevelFilter {
+        match (quiet, verbose) {
+            (0, 0) => LevelFilter::Info,
+            (0, 1) => LevelFilter::Debug,
+            (0, _) => LevelFilter::Trace,
+            (1, _) => LevelFilter::Warn,
+            (_, _) => LevelFilter::Error,
+        }
+    }
+
+    /// Runs the command, its command line is logged before it and its exit code and duration after it
+    pub fn timed<T>(command_line: &str, run: impl FnOnce() -> io::Result<T>) -> io::Result<T> {
+        debug!("{}", tr!(CommandStarted, command_line));
+        let started = Instant::now();
+        let result = run();
+        let exit_code = AuditLog::exit_code_of(&result)
+            .map(|exit_code| exit_code.to_string())
+            .unwrap_or(String::from("-"));
+        debug!("{}", tr!(CommandFinished, command_line, exit_code, started.elapsed().as_millis()));
+        result
+    }
+
+    /// The output of commands is only printed from the debug level, it is mostly progress of git and maven
+    fn console_level_of(record: &Record) -> Level {
+        if record.target().starts_with(COMMAND_OUTPUT_TARGET) && record.level() == Level::Info {
+            Level::Debug
+        } else {
+            record.level()
+        }
+    }
+
+    fn print(&self, record: &Record) {
+        if !self.colored {
+            eprintln!("{}", record.args());
+            return;
+        }
+        match record.level() {
+            Level::Error => e_red_ln!("{}", record.args()),
+            Level::Warn => e_dark_yellow_ln!("{}", record.args()),
+            Level::Info if record.target() == SUCCESS_TARGET => e_green_ln!("{}", record.args()),
+            Level::Info => e_blue_ln!("{}", record.args()),
+            Level::Debug | Level::Trace => e_grey_ln!("{}", record.args()),
+        }
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.log_file.is_some() || metadata.level() <= self.console_level
+    }
+
+    fn log(&self, record: &Record) {
+        if Self::console_level_of(record) <= self.console_level {
+            self.print(record);
+        }
+        if let Some(log_file) = &self.log_file {
+            let time = Local::now().to_rfc3339_opts(SecondsFormat::Millis, false);
+            let mut log_file = log_file.lock().unwrap();
+            let _ = writeln!(log_file, "{} {:<5} {}", time, record.level(), record.args());
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(log_file) = &self.log_file {
+            let _ = log_file.lock().unwrap().flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use log::LevelFilter;
+
+    use crate::support::logger::Logger;
+
+    #[test]
+    fn level_of_flags() {
+        assert_eq!(LevelFilter::Info, Logger::level_of(0, 0));
+        assert_eq!(LevelFilter::Debug, Logger::level_of(0, 1));
+        assert_eq!(LevelFilter::Trace, Logger::level_of(0, 2));
+        assert_eq!(LevelFilter::Warn, Logger::level_of(1, 0));
+        assert_eq!(LevelFilter::Error, Logger::level_of(2, 0));
+    }
+}
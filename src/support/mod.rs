@@ -1,4 +1,13 @@
 pub mod enums;
 pub mod prompt;
 pub mod util;
-pub mod parameter;
\ No newline at end of file
+pub mod parameter;
+pub mod argument;
+pub mod version;
+pub mod i18n;
+pub mod audit;
+pub mod failure;
+pub mod config;
+pub mod replacer;
+pub mod report;
+pub mod logger;
@@ -1,17 +1,32 @@
-use colour::e_blue_ln;
+use std::borrow::Cow;
+
+use log::info;
+use serde::{Deserialize, Serialize};
 use tabled::{Style, Table, Tabled};
 
-use crate::{FlowType, ReleaseType};
 use crate::action::git_flow_action_adapter::GitFlowActionAdapter;
 use crate::action::maven_git_flow_action_adapter::MavenGitFlowActionAdapter;
 use crate::action::webpack_git_flow_action_adapter::WebpackGitFlowActionAdapter;
-use crate::support::enums::ProjectType;
+use crate::action::helm_git_flow_action_adapter::HelmGitFlowActionAdapter;
+use crate::execution::cli_git_backend::CliGitBackend;
+use crate::execution::git_backend::GitBackend;
+use crate::execution::libgit2_git_backend::Libgit2GitBackend;
+use crate::support::enums::{FlowType, GitBackendType, ProjectType, ReleaseType};
+use crate::tr;
 
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
 pub struct GitFlowParameter {
     pub project_type: ProjectType,
     pub flow_type: FlowType,
     pub release_type: Option<ReleaseType>,
     pub specific_branch_name: Option<String>,
+    pub base_tag: Option<String>,
+    pub support_branch_name: Option<String>,
+    #[serde(default)]
+    pub major: bool,
+    pub git_backend_type: GitBackendType,
     pub need_push: bool,
     pub need_push_tag: bool,
 }
@@ -23,6 +38,10 @@ impl GitFlowParameter {
             flow_type,
             release_type: None,
             specific_branch_name: None,
+            base_tag: None,
+            support_branch_name: None,
+            major: false,
+            git_backend_type: GitBackendType::Cli,
             need_push: false,
             need_push_tag: false,
         }
@@ -38,6 +57,26 @@ impl GitFlowParameter {
         self
     }
 
+    pub fn set_base_tag(&mut self, base_tag: String) -> &mut GitFlowParameter {
+        self.base_tag = Some(base_tag);
+        self
+    }
+
+    pub fn set_support_branch_name(&mut self, support_branch_name: String) -> &mut GitFlowParameter {
+        self.support_branch_name = Some(support_branch_name);
+        self
+    }
+
+    pub fn set_major(&mut self, major: bool) -> &mut GitFlowParameter {
+        self.major = major;
+        self
+    }
+
+    pub fn set_git_backend_type(&mut self, git_backend_type: GitBackendType) -> &mut GitFlowParameter {
+        self.git_backend_type = git_backend_type;
+        self
+    }
+
     pub fn set_need_push(&mut self, need_push: bool) -> &mut GitFlowParameter {
         self.need_push = need_push;
         self
@@ -49,52 +88,101 @@ impl GitFlowParameter {
     }
 
     pub fn get_git_flow_action_adapter(&mut self) -> Box<dyn GitFlowActionAdapter> {
-        if ProjectType::Maven.eq(&self.project_type) {
-            Box::new(MavenGitFlowActionAdapter {})
-        } else {
-            Box::new(WebpackGitFlowActionAdapter {})
-        }
+        git_flow_action_adapter_of(&self.project_type)
+    }
+
+    pub fn get_git_backend(&mut self) -> Box<dyn GitBackend> {
+        git_backend_of(&self.git_backend_type)
     }
 
     pub fn print_parameters(&mut self) {
         let mut print_contents = Vec::new();
         print_contents.push(PrintContent {
-            option: String::from("项目类型"),
-            select_value: String::from(self.project_type.to_string()),
+            option: tr!(ParameterProjectType),
+            select_value: self.project_type.to_string(),
         });
         print_contents.push(PrintContent {
-            option: String::from("流程类型"),
-            select_value: String::from(self.flow_type.to_string()),
+            option: tr!(ParameterFlowType),
+            select_value: self.flow_type.to_string(),
         });
-        if self.release_type.is_some() {
+        if let Some(release_type) = &self.release_type {
             print_contents.push(PrintContent {
-                option: String::from("Release类型"),
-                select_value: String::from(self.release_type.as_mut().unwrap().to_string()),
+                option: tr!(ParameterReleaseType),
+                select_value: release_type.to_string(),
             });
         }
-        if self.specific_branch_name.is_some() {
+        if let Some(specific_branch_name) = &self.specific_branch_name {
             print_contents.push(PrintContent {
-                option: String::from("指定的Release分支"),
-                select_value: String::from(self.specific_branch_name.as_mut().unwrap().to_string()),
+                option: tr!(ParameterSpecificBranch),
+                select_value: specific_branch_name.to_string(),
+            });
+        }
+        if let Some(base_tag) = &self.base_tag {
+            print_contents.push(PrintContent {
+                option: tr!(ParameterBaseTag),
+                select_value: base_tag.to_string(),
+            });
+        }
+        if let Some(support_branch_name) = &self.support_branch_name {
+            print_contents.push(PrintContent {
+                option: tr!(ParameterSupportBranch),
+                select_value: support_branch_name.to_string(),
+            });
+        }
+        if self.major {
+            print_contents.push(PrintContent {
+                option: tr!(ParameterMajor),
+                select_value: self.major.to_string(),
             });
         }
         print_contents.push(PrintContent {
-            option: String::from("是否推送分支到远端"),
-            select_value: String::from(self.need_push.to_string()),
+            option: tr!(ParameterGitBackend),
+            select_value: self.git_backend_type.to_string(),
         });
         print_contents.push(PrintContent {
-            option: String::from("是否推送Tag到远端"),
-            select_value: String::from(self.need_push_tag.to_string()),
+            option: tr!(ParameterNeedPush),
+            select_value: self.need_push.to_string(),
+        });
+        print_contents.push(PrintContent {
+            option: tr!(ParameterNeedPushTag),
+            select_value: self.need_push_tag.to_string(),
         });
         let table = Table::new(print_contents)
             .with(Style::modern())
             .to_string();
-        e_blue_ln!("{}",table);
+        info!("{}",table);
+    }
+}
+
+pub fn git_flow_action_adapter_of(project_type: &ProjectType) -> Box<dyn GitFlowActionAdapter> {
+    match project_type {
+        ProjectType::Maven => Box::new(MavenGitFlowActionAdapter {}),
+        ProjectType::Webpack => Box::new(WebpackGitFlowActionAdapter {}),
+        ProjectType::Helm => Box::new(HelmGitFlowActionAdapter {}),
+    }
+}
+
+pub fn git_backend_of(git_backend_type: &GitBackendType) -> Box<dyn GitBackend> {
+    if GitBackendType::Libgit2.eq(git_backend_type) {
+        Box::new(Libgit2GitBackend::open())
+    } else {
+        Box::new(CliGitBackend {})
     }
 }
 
-#[derive(Tabled)]
 struct PrintContent {
     option: String,
     select_value: String,
 }
+
+impl Tabled for PrintContent {
+    const LENGTH: usize = 2;
+
+    fn fields(&self) -> Vec<Cow<'_, str>> {
+        vec![Cow::Borrowed(&self.option), Cow::Borrowed(&self.select_value)]
+    }
+
+    fn headers() -> Vec<Cow<'static, str>> {
+        vec![Cow::Owned(tr!(HeaderOption)), Cow::Owned(tr!(HeaderValue))]
+    }
+}
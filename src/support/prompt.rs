@@ -1,51 +1,195 @@
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::fmt;
 use std::str::FromStr;
 
-use inquire::{Confirm, InquireError, Select};
+use chrono::{DateTime, Local};
+use inquire::{Confirm, CustomUserError, InquireError, MultiSelect, Select};
 
-use crate::GitExecution;
+use crate::action::common_git_flow_action::CommonGitFlowAction;
+use crate::action::git_flow_action_adapter::GitFlowActionAdapter;
+use crate::command::reference_of;
+use crate::execution::git_backend::GitBackend;
+use crate::flow::git_flow::GitFlow;
 use crate::support::enums::{FlowType, ProjectType, ReleaseType};
+use crate::tr;
+use crate::support::util::Util;
+use crate::support::version::VersionUtil;
+
+/// A branch which can be picked for the specific release
+struct BranchOption {
+    branch_name: String,
+    version: String,
+    commit_time: Option<i64>,
+    remote_only: bool,
+}
+
+impl fmt::Display for BranchOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let commit_date = self.commit_time
+            .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+            .map(|date_time| date_time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or(String::from("-"));
+        let location = if self.remote_only { "(origin)" } else { "" };
+        write!(f, "{:<32} {:<20} {} {}", self.branch_name, self.version, commit_date, location)
+    }
+}
 
 pub fn get_package_type_from_prompt() -> Result<ProjectType, InquireError> {
-    let options: Vec<&str> = vec!["Maven", "Webpack"];
-    let result = Select::new("请选择当前项目的类型...", options)
+    let options: Vec<&str> = vec!["Maven", "Webpack", "Helm"];
+    Select::new(&tr!(PromptProjectType), options)
         .prompt()
-        .and_then(|str| ProjectType::from_str(str));
-    return result;
+        .and_then(ProjectType::from_str)
 }
 
 pub fn get_flow_type_from_prompt() -> Result<FlowType, InquireError> {
-    let options: Vec<&str> = vec!["Feature", "Hotfix", "Release"];
-    let result = Select::new("请选择要执行的流程...", options)
+    let options: Vec<&str> = vec!["Feature", "Hotfix", "Release", "Support"];
+    Select::new(&tr!(PromptFlowType), options)
         .prompt()
-        .and_then(|str| FlowType::from_str(str));
-    return result;
+        .and_then(FlowType::from_str)
 }
 
 pub fn get_release_type_from_prompt() -> Result<ReleaseType, InquireError> {
-    let options: Vec<&str> = vec!["Hotfix", "Test", "Specific"];
-    let result = Select::new("请选择一个Release类型...", options)
+    let options: Vec<&str> = vec!["Hotfix", "Test", "Specific", "Candidate"];
+    Select::new(&tr!(PromptReleaseType), options)
         .prompt()
-        .and_then(|str| ReleaseType::from_str(str));
-    return result;
+        .and_then(ReleaseType::from_str)
 }
 
 pub fn confirm_execution_prompt() -> Result<bool, InquireError> {
-    let confirm_result = Confirm::new("是否确认执行上述操作?")
+    Confirm::new(&tr!(PromptConfirm))
         .with_default(false)
         // .with_help_message("This data is stored for good reasons")
-        .prompt();
-    return confirm_result;
+        .prompt()
+}
+
+pub fn get_branch_name_from_select(
+    git_flow_action_adapter: &dyn GitFlowActionAdapter,
+    git_backend: &dyn GitBackend,
+) -> Result<String, InquireError> {
+    git_backend.fetch();
+    let options = branch_options(git_flow_action_adapter, git_backend);
+    if options.is_empty() {
+        return Err(InquireError::Custom(CustomUserError::from(tr!(NoDevelopmentBranch))));
+    }
+    let option = Select::new(&tr!(PromptSpecificBranch), options)
+        .with_filter(&|filter, _, string_value, _| Util::fuzzy_match(filter, string_value))
+        .with_page_size(15)
+        .prompt()?;
+    if option.remote_only {
+        git_backend.create_tracking_branch(&option.branch_name);
+    }
+    Ok(option.branch_name)
+}
+
+/// The local and remote branches developing a version, from the latest commit to the earliest
+fn branch_options(git_flow_action_adapter: &dyn GitFlowActionAdapter, git_backend: &dyn GitBackend) -> Vec<BranchOption> {
+    let local_branches = git_backend.list_all_branch().into_iter().collect::<BTreeSet<String>>();
+    let all_branches = local_branches
+        .iter()
+        .cloned()
+        .chain(git_backend.list_remote_branch())
+        .collect::<BTreeSet<String>>();
+    let mut options = all_branches
+        .into_iter()
+        .filter_map(|branch_name| {
+            let reference = reference_of(&branch_name, &local_branches);
+            let version = git_flow_action_adapter.get_project_version_at(git_backend, &reference)?;
+            if !VersionUtil::is_development_version(&version) {
+                return None;
+            }
+            Some(BranchOption {
+                commit_time: git_backend.get_commit_time(&reference),
+                remote_only: !local_branches.contains(&branch_name),
+                branch_name,
+                version,
+            })
+        })
+        .collect::<Vec<BranchOption>>();
+    options.sort_by_key(|option| Reverse(option.commit_time));
+    options
 }
 
-pub fn get_branch_name_from_select() -> Result<String, InquireError> {
-    let branch_vec = GitExecution::list_all_branch();
-    Select::new("请选择要特殊Release的分支名称", branch_vec)
+/// Selects a support branch, local or remote
+pub fn select_support_branch(git_backend: &dyn GitBackend) -> Result<String, InquireError> {
+    git_backend.fetch();
+    let local_branches = git_backend.list_all_branch().into_iter().collect::<BTreeSet<String>>();
+    let support_branches = local_branches.iter()
+        .cloned()
+        .chain(git_backend.list_remote_branch())
+        .filter(|branch_name| branch_name.starts_with(&(CommonGitFlowAction::SUPPORT_BRANCH.to_owned() + "/")))
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect::<Vec<String>>();
+    if support_branches.is_empty() {
+        return Err(InquireError::Custom(CustomUserError::from(tr!(NoSupportBranch))));
+    }
+    let branch_name = Select::new(&tr!(PromptSupportBranch), support_branches)
+        .with_filter(&|filter, _, string_value, _| Util::fuzzy_match(filter, string_value))
+        .prompt()?;
+    if !local_branches.contains(&branch_name) {
+        git_backend.create_tracking_branch(&branch_name);
+    }
+    Ok(branch_name)
+}
+
+/// Selects a release tag, from the highest version
+pub fn select_release_tag(git_backend: &dyn GitBackend) -> Result<String, InquireError> {
+    git_backend.fetch();
+    let tag_names = VersionUtil::sort_release_tags(git_backend.list_tags());
+    if tag_names.is_empty() {
+        return Err(InquireError::Custom(CustomUserError::from(tr!(NoReleaseTag))));
+    }
+    Select::new(&tr!(PromptReleaseTag), tag_names)
+        .with_filter(&|filter, _, string_value, _| Util::fuzzy_match(filter, string_value))
+        .with_page_size(15)
         .prompt()
-        .map(|str| String::from(str))
 }
 
 pub fn select_true_or_false(prompt_content: &str) -> Result<bool, InquireError> {
     let branch_vec = vec![true, false];
     Select::new(prompt_content, branch_vec)
         .prompt()
-}
\ No newline at end of file
+}
+pub fn select_branches_to_delete(branch_vec: Vec<String>) -> Result<Vec<String>, InquireError> {
+    let all_selected = (0..branch_vec.len()).collect::<Vec<usize>>();
+    MultiSelect::new(&tr!(PromptBranchesToDelete), branch_vec)
+        .with_default(&all_selected)
+        .prompt()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::memory_git_flow_action_adapter::MemoryGitFlowActionAdapter;
+    use crate::execution::memory_git_backend::{MemoryGitBackend, MemoryRepository};
+    use crate::support::prompt::branch_options;
+
+    #[test]
+    fn branch_options_of_development_versions() {
+        let repository = MemoryRepository::new("1.0.0-SNAPSHOT")
+            .with_commit("master", "release", "1.2.0.RELEASE")
+            .with_branch("develop", "master")
+            .with_commit("develop", "back to", "1.3.0-SNAPSHOT")
+            .with_branch("feature/1.3.0", "develop")
+            .with_branch("hotfix/1.2.1", "master")
+            .with_commit("hotfix/1.2.1", "new hotfix", "1.2.1-SNAPSHOT")
+            .with_everything_pushed()
+            .with_remote_only("hotfix/1.2.1")
+            .shared();
+        let options = branch_options(&MemoryGitFlowActionAdapter::new(repository.clone()), &MemoryGitBackend::new(repository.clone()));
+        // commits are created in order, so the hotfix is the latest and develop shares its commit with the feature
+        let rows = options.iter()
+            .map(|option| (option.branch_name.as_str(), option.version.as_str(), option.commit_time, option.remote_only))
+            .collect::<Vec<(&str, &str, Option<i64>, bool)>>();
+        assert_eq!(
+            vec![
+                ("hotfix/1.2.1", "1.2.1-SNAPSHOT", Some(3), true),
+                ("develop", "1.3.0-SNAPSHOT", Some(2), false),
+                ("feature/1.3.0", "1.3.0-SNAPSHOT", Some(2), false),
+            ],
+            rows
+        );
+        assert!(options[0].to_string().ends_with("(origin)"));
+        assert!(options[1].to_string().starts_with("develop "));
+    }
+}
@@ -0,0 +1,124 @@
+use std::fs;
+use std::path::PathBuf;
+
+use log::info;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::tr;
+
+/// The capture group holding the version, the first group is used when there is none with this name
+const VERSION_GROUP: &str = "version";
+
+/// Updates the version kept in files besides the project file, `LABEL version="1.3.0"` of `Dockerfile`
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VersionReplacer {
+    /// Glob of the files relative to the working directory, `helm/*/Chart.yaml`
+    pub file: String,
+    /// Regex capturing the version to replace by the `version` group, or by the first group
+    pub pattern: String,
+}
+
+impl VersionReplacer {
+    /// Checks the glob and the regex, and that the regex matches in the files
+    pub fn validate(&self) -> Result<(), String> {
+        let regex = self.regex()?;
+        let match_count = self.files()?
+            .iter()
+            .map(|path| Self::read(path).map(|content| regex.find_iter(&content).count()))
+            .sum::<Result<usize, String>>()?;
+        if match_count == 0 {
+            return Err(tr!(ReplacerMatchesNothing, self.file, self.pattern));
+        }
+        Ok(())
+    }
+
+    /// Replaces the captured versions with the new version in all the files
+    pub fn replace(&self, new_version: &str) -> Result<(), String> {
+        let regex = self.regex()?;
+        let mut match_count = 0;
+        for path in self.files()? {
+            let content = Self::read(&path)?;
+            let mut new_content = String::with_capacity(content.len());
+            let mut last_end = 0;
+            let previous_match_count = match_count;
+            for captures in regex.captures_iter(&content) {
+                let Some(version) = captures.name(VERSION_GROUP).or(captures.get(1)) else {
+                    continue;
+                };
+                new_content.push_str(&content[last_end..version.start()]);
+                new_content.push_str(new_version);
+                last_end = version.end();
+                match_count += 1;
+            }
+            if match_count == previous_match_count {
+                continue;
+            }
+            new_content.push_str(&content[last_end..]);
+            info!("{}", tr!(ReplacerUpdateVersion, path.display(), new_version));
+            if let Err(err) = fs::write(&path, new_content) {
+                return Err(tr!(WriteFileFailed, path.display(), err));
+            }
+        }
+        if match_count == 0 {
+            return Err(tr!(ReplacerMatchesNothing, self.file, self.pattern));
+        }
+        Ok(())
+    }
+
+    fn regex(&self) -> Result<Regex, String> {
+        let regex = Regex::new(&self.pattern)
+            .map_err(|err| tr!(InvalidReplacerPattern, self.pattern, err))?;
+        if regex.captures_len() < 2 {
+            return Err(tr!(InvalidReplacerPattern, self.pattern, tr!(ReplacerNoCapture)));
+        }
+        Ok(regex)
+    }
+
+    fn files(&self) -> Result<Vec<PathBuf>, String> {
+        let paths = glob::glob(&self.file)
+            .map_err(|err| tr!(InvalidReplacerFile, self.file, err))?;
+        Ok(paths.flatten().filter(|path| path.is_file()).collect::<Vec<PathBuf>>())
+    }
+
+    fn read(path: &PathBuf) -> Result<String, String> {
+        fs::read_to_string(path).map_err(|err| tr!(ReadFileFailed, path.display(), err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::support::replacer::VersionReplacer;
+
+    fn replacer(file: &str, pattern: &str) -> VersionReplacer {
+        VersionReplacer {
+            file: String::from(file),
+            pattern: String::from(pattern),
+        }
+    }
+
+    #[test]
+    fn replace_captured_versions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let directory = temp_dir.path();
+        fs::create_dir_all(directory.join("helm")).unwrap();
+        let chart = directory.join("helm").join("Chart.yaml");
+        fs::write(&chart, "name: app\nversion: 1.2.0\nappVersion: \"1.2.0\"\n").unwrap();
+        let glob = directory.join("helm").join("*.yaml").display().to_string();
+
+        let app_version_replacer = replacer(&glob, r#"appVersion: "(?P<version>[^"]+)""#);
+        app_version_replacer.validate().unwrap();
+        app_version_replacer.replace("1.3.0-SNAPSHOT").unwrap();
+        assert_eq!("name: app\nversion: 1.2.0\nappVersion: \"1.3.0-SNAPSHOT\"\n", fs::read_to_string(&chart).unwrap());
+
+        assert!(replacer(&glob, r"tag: (\S+)").validate().is_err());
+        assert!(replacer(&glob, r"version: \S+").validate().is_err());
+        assert!(replacer(&glob, r"version: (\S+").validate().is_err());
+        assert!(replacer(&directory.join("*.txt").display().to_string(), r"version: (\S+)").validate().is_err());
+    }
+}
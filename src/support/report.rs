@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::sync::Mutex;
+
+use serde::Serialize;
+
+use crate::execution::git_backend::GitBackend;
+use crate::support::audit::{ref_changes_between, Outcome};
+use crate::support::enums::{FlowType, GitBackendType, OutputFormat, ProjectType, ReleaseType};
+use crate::support::parameter::{git_backend_of, GitFlowParameter};
+
+const BRANCH_REF_PREFIX: &str = "refs/heads/";
+
+/// The report of the flow in execution, only recorded when it is printed as JSON
+static CURRENT_REPORT: Mutex<Option<FlowReport>> = Mutex::new(None);
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Serialize)]
+pub struct CommitReport {
+    pub id: Option<String>,
+    pub message: String,
+}
+
+/// What the flow did, for the pipelines running it
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Clone)]
+#[derive(Serialize)]
+pub struct FlowReport {
+    pub outcome: Option<Outcome>,
+    pub message: Option<String>,
+    pub flow_type: Option<FlowType>,
+    pub release_type: Option<ReleaseType>,
+    pub project_type: Option<ProjectType>,
+    pub previous_version: Option<String>,
+    pub released_version: Option<String>,
+    pub next_development_version: Option<String>,
+    pub tag: Option<String>,
+    pub created_branches: Vec<String>,
+    pub moved_branches: Vec<String>,
+    pub deleted_branches: Vec<String>,
+    pub commits: Vec<CommitReport>,
+    #[serde(skip)]
+    git_backend_type: Option<GitBackendType>,
+    #[serde(skip)]
+    refs_before: BTreeMap<String, String>,
+}
+
+impl FlowReport {
+    /// Sorts the changed branches out of the refs listed before and after the flow
+    fn record_branches(&mut self, refs_after: &BTreeMap<String, String>) {
+        for ref_change in ref_changes_between(&self.refs_before, refs_after) {
+            let Some(branch_name) = ref_change.name.strip_prefix(BRANCH_REF_PREFIX).map(String::from) else {
+                continue;
+            };
+            match (ref_change.before, ref_change.after) {
+                (None, _) => self.created_branches.push(branch_name),
+                (_, None) => self.deleted_branches.push(branch_name),
+                _ => self.moved_branches.push(branch_name),
+            }
+        }
+    }
+}
+
+pub struct FlowReporter {}
+
+impl FlowReporter {
+    /// Starts recording the report if it is printed as JSON
+    pub fn start(output_format: &OutputFormat) {
+        if OutputFormat::Json.eq(output_format) {
+            *CURRENT_REPORT.lock().unwrap() = Some(FlowReport::default());
+        }
+    }
+
+    pub fn is_recording() -> bool {
+        CURRENT_REPORT.lock().unwrap().is_some()
+    }
+
+    /// Records the parameters of the flow and the refs before it changes anything
+    pub fn start_flow(parameters: &GitFlowParameter, git_backend: &dyn GitBackend) {
+        if !Self::is_recording() {
+            return;
+        }
+        let refs_before = git_backend.list_refs();
+        if let Some(report) = CURRENT_REPORT.lock().unwrap().as_mut() {
+            report.flow_type = Some(parameters.flow_type.clone());
+            report.release_type = parameters.release_type.clone();
+            report.project_type = Some(parameters.project_type.clone());
+            report.git_backend_type = Some(parameters.git_backend_type.clone());
+            report.refs_before = refs_before;
+        }
+    }
+
+    /// Records a version set by the flow, the first old version is the version before the flow
+    pub fn record_version_change(old_version: Option<&str>, new_version: &str) {
+        if let Some(report) = CURRENT_REPORT.lock().unwrap().as_mut() {
+            if report.next_development_version.is_none() && report.released_version.is_none() {
+                report.previous_version = old_version.map(String::from);
+            }
+            report.next_development_version = Some(String::from(new_version));
+        }
+    }
+
+    /// Records the tag, the version set last is the version released by it
+    pub fn record_tag(tag_name: &str) {
+        if let Some(report) = CURRENT_REPORT.lock().unwrap().as_mut() {
+            report.tag = Some(String::from(tag_name));
+            report.released_version = report.next_development_version.take();
+        }
+    }
+
+    pub fn record_commit(id: Option<String>, message: &str) {
+        if let Some(report) = CURRENT_REPORT.lock().unwrap().as_mut() {
+            report.commits.push(CommitReport { id, message: String::from(message) });
+        }
+    }
+
+    /// Records the branches changed by the flow and prints the report
+    pub fn finish(git_backend: &dyn GitBackend) {
+        let current_report = CURRENT_REPORT.lock().unwrap().take();
+        if let Some(mut report) = current_report {
+            report.record_branches(&git_backend.list_refs());
+            report.outcome = Some(Outcome::Success);
+            Self::print(&report);
+        }
+    }
+
+    /// Records the failure and the branches changed before it, then prints the report
+    pub fn finish_with_failure(message: &str) {
+        let current_report = CURRENT_REPORT.lock().unwrap().take();
+        if let Some(mut report) = current_report {
+            if let Some(git_backend_type) = &report.git_backend_type {
+                report.record_branches(&git_backend_of(git_backend_type).list_refs());
+            }
+            report.outcome = Some(Outcome::Failure);
+            report.message = Some(String::from(message));
+            Self::print(&report);
+        }
+    }
+
+    fn print(report: &FlowReport) {
+        let mut stdout = io::stdout().lock();
+        // stdout only holds the report, nothing is left to log a failure to
+        if let Ok(json) = serde_json::to_string_pretty(report) {
+            let _ = writeln!(stdout, "{}", json);
+            let _ = stdout.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::support::report::FlowReport;
+
+    #[test]
+    fn record_branches() {
+        let mut report = FlowReport {
+            refs_before: BTreeMap::from([
+                (String::from("refs/heads/develop"), String::from("a")),
+                (String::from("refs/heads/test/1.3.0"), String::from("b")),
+                (String::from("refs/tags/v1.2.0"), String::from("c")),
+            ]),
+            ..FlowReport::default()
+        };
+        report.record_branches(&BTreeMap::from([
+            (String::from("refs/heads/develop"), String::from("d")),
+            (String::from("refs/heads/feature/1.4.0"), String::from("d")),
+            (String::from("refs/tags/v1.2.0"), String::from("c")),
+            (String::from("refs/tags/v1.3.0"), String::from("d")),
+        ]));
+        assert_eq!(vec!["feature/1.4.0"], report.created_branches);
+        assert_eq!(vec!["develop"], report.moved_branches);
+        assert_eq!(vec!["test/1.3.0"], report.deleted_branches);
+    }
+}
@@ -1,7 +1,7 @@
 pub struct Util {}
 
 impl Util {
-    pub fn substring_between(line: &String, start: &str, end: &str) -> Option<String> {
+    pub fn substring_between(line: &str, start: &str, end: &str) -> Option<String> {
         let start_bytes = line.find(start);
         let end_bytes = line.find(end);
         if start_bytes.is_none() || end_bytes.is_none() {
@@ -11,21 +11,39 @@ impl Util {
         Some(String::from(result))
     }
 
-    pub fn substring_after(line: &String, start: &str) -> Option<String> {
-        let start_bytes = line.find(start);
-        if start_bytes.is_none() {
-            return None;
-        }
-        let result = &line[start_bytes.unwrap() + 1..line.len()];
+    pub fn substring_after(line: &str, start: &str) -> Option<String> {
+        let start_bytes = line.find(start)?;
+        let result = &line[start_bytes + 1..line.len()];
         Some(String::from(result))
     }
 
-    pub fn substring_before(line: &String, end: &str) -> Option<String> {
-        let end_bytes = line.find(end);
-        if end_bytes.is_none() {
-            return None;
-        }
-        let result = &line[0..end_bytes.unwrap()];
+    pub fn substring_before(line: &str, end: &str) -> Option<String> {
+        let end_bytes = line.find(end)?;
+        let result = &line[0..end_bytes];
         Some(String::from(result))
     }
-}
\ No newline at end of file
+
+    /// Whether all characters of pattern appear in value in the same order, ignoring case
+    pub fn fuzzy_match(pattern: &str, value: &str) -> bool {
+        let mut value_chars = value.chars().flat_map(char::to_lowercase);
+        pattern
+            .chars()
+            .flat_map(char::to_lowercase)
+            .filter(|c| !c.is_whitespace())
+            .all(|pattern_char| value_chars.any(|value_char| value_char == pattern_char))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::support::util::Util;
+
+    #[test]
+    fn fuzzy_match() {
+        assert!(Util::fuzzy_match("", "feature/1.3.0"));
+        assert!(Util::fuzzy_match("f13", "feature/1.3.0"));
+        assert!(Util::fuzzy_match("FT 1.3", "feature/1.3.0"));
+        assert!(!Util::fuzzy_match("31", "feature/1.3.0"));
+        assert!(!Util::fuzzy_match("hotfix", "feature/1.3.0"));
+    }
+}
@@ -0,0 +1,488 @@
+use std::sync::OnceLock;
+
+use chrono::{Datelike, NaiveDate, Utc};
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::support::util::Util;
+use crate::tr;
+
+/// The suffix of development versions
+pub const SNAPSHOT_SUFFIX: &str = "-SNAPSHOT";
+/// The suffix of release versions
+pub const RELEASE_SUFFIX: &str = ".RELEASE";
+/// The version number released when there is no release tag yet
+pub const INITIAL_VERSION_NUMBER: &str = "1.0.0";
+/// The sequence token of calendar patterns, counts the releases of the same period from 0
+const SEQUENCE_TOKEN: &str = "SEQ";
+/// The placeholders of release tag formats
+const VERSION_PLACEHOLDER: &str = "{version}";
+const DATE_PLACEHOLDER: &str = "{date}";
+
+static TAG_FORMAT: OnceLock<TagFormat> = OnceLock::new();
+
+pub struct VersionUtil {}
+
+impl VersionUtil {
+    /// Extract version number from release tag of the tag format, `v1.2.0.RELEASE.20230101` to `1.2.0`
+    pub fn version_number_of_tag(tag_name: &str) -> Option<String> {
+        tag_format().version_number_of(tag_name)
+    }
+
+    /// Extract version number from development version, `1.3.0-SNAPSHOT` to `1.3.0`
+    pub fn version_number_of_snapshot(version: &str) -> Option<String> {
+        Util::substring_before(version.trim_start_matches('v'), SNAPSHOT_SUFFIX)
+    }
+
+    /// Whether the version is still under development, `1.3.0-SNAPSHOT` or other pre-release versions like `1.3.0-beta`
+    pub fn is_development_version(version: &str) -> bool {
+        version.ends_with(SNAPSHOT_SUFFIX) || (!version.contains(RELEASE_SUFFIX) && version.contains('-'))
+    }
+
+    /// Whether it is made of dot separated numbers only, `1.3` and `1.3.0` but not `1.3.0-SNAPSHOT`
+    pub fn is_version_number(version_number: &str) -> bool {
+        Self::numbers_of(version_number).is_some()
+    }
+
+    /// Increase major and reset minor and patch, `1.2.3` to `2.0.0`
+    pub fn next_major(version_number: &str) -> Option<String> {
+        let mut numbers = Self::split(version_number)?;
+        numbers[0] += 1;
+        numbers[1] = 0;
+        numbers[2] = 0;
+        Some(Self::join(&numbers))
+    }
+
+    /// Increase minor and reset patch, `1.2.3` to `1.3.0`
+    pub fn next_minor(version_number: &str) -> Option<String> {
+        let mut numbers = Self::split(version_number)?;
+        numbers[1] += 1;
+        numbers[2] = 0;
+        Some(Self::join(&numbers))
+    }
+
+    /// Increase patch, `1.2.3` to `1.2.4`
+    pub fn next_patch(version_number: &str) -> Option<String> {
+        let mut numbers = Self::split(version_number)?;
+        numbers[2] += 1;
+        Some(Self::join(&numbers))
+    }
+
+    /// The version to release by the test flow, next minor of the last release
+    pub fn next_release_test_version_number(last_tag_name: Option<String>) -> String {
+        last_tag_name
+            .and_then(|tag_name| Self::version_number_of_tag(&tag_name))
+            .and_then(|version_number| Self::next_minor(&version_number))
+            .unwrap_or(String::from(INITIAL_VERSION_NUMBER))
+    }
+
+    /// The version to release by the major release, next major of the last release
+    pub fn next_major_release_version_number(last_tag_name: Option<String>) -> String {
+        last_tag_name
+            .and_then(|tag_name| Self::version_number_of_tag(&tag_name))
+            .and_then(|version_number| Self::next_major(&version_number))
+            .unwrap_or(String::from(INITIAL_VERSION_NUMBER))
+    }
+
+    /// The version of hotfix, next patch of the last release
+    pub fn next_hotfix_version_number(last_tag_name: Option<String>) -> Option<String> {
+        last_tag_name
+            .and_then(|tag_name| Self::version_number_of_tag(&tag_name))
+            .and_then(|version_number| Self::next_patch(&version_number))
+    }
+
+    /// Next patch within the minor line of the version number, after the latest release of that line,
+    /// `1.3.4` to `1.3.6` when `v1.3.5.RELEASE.20230101` is released
+    pub fn next_patch_in_line(version_number: &str, tag_names: &[String]) -> Option<String> {
+        let numbers = Self::split(version_number)?;
+        let latest_patch = tag_names.iter()
+            .filter_map(|tag_name| Self::version_number_of_tag(tag_name))
+            .filter_map(|tag_version_number| Self::split(&tag_version_number))
+            .filter(|tag_numbers| tag_numbers[..2] == numbers[..2])
+            .map(|tag_numbers| tag_numbers[2])
+            .fold(numbers[2], u64::max);
+        Some(Self::join(&[numbers[0], numbers[1], latest_patch + 1]))
+    }
+
+    /// The minor line of the version number, `1.3.4` to `1.3`
+    pub fn minor_line_of(version_number: &str) -> Option<String> {
+        let numbers = Self::split(version_number)?;
+        Some(format!("{}.{}", numbers[0], numbers[1]))
+    }
+
+    /// The release tags from the highest version to the lowest, the other tags are skipped
+    pub fn sort_release_tags(tag_names: Vec<String>) -> Vec<String> {
+        let mut release_tags = tag_names.into_iter()
+            .filter_map(|tag_name| {
+                let numbers = Self::numbers_of(&Self::version_number_of_tag(&tag_name)?)?;
+                Some((numbers, tag_name))
+            })
+            .collect::<Vec<(Vec<u64>, String)>>();
+        release_tags.sort_by(|left, right| right.cmp(left));
+        release_tags.into_iter()
+            .map(|(_, tag_name)| tag_name)
+            .collect::<Vec<String>>()
+    }
+
+    /// The version of the release candidate, `1.3.0` to `1.3.0-RC.2`
+    pub fn candidate_version(version_number: &str, identifier: &str, candidate_number: u64) -> String {
+        format!("{}-{}.{}", version_number, identifier, candidate_number)
+    }
+
+    /// The tag name of the release candidate by the tag format, `1.3.0-RC.2` to `v1.3.0-RC.2`
+    pub fn candidate_tag_name(candidate_version: &str) -> String {
+        tag_format().candidate_tag_name(candidate_version)
+    }
+
+    /// The numbers of the version's release candidates tagged, from the highest to the lowest
+    pub fn candidate_numbers(version_number: &str, identifier: &str, tag_names: &[String]) -> Vec<u64> {
+        tag_format().candidate_numbers(version_number, identifier, tag_names)
+    }
+
+    /// The tag name of version number released today by the tag format, `1.2.0` to `v1.2.0.RELEASE.20230101`
+    pub fn release_tag_name(version_number: &str) -> String {
+        tag_format().tag_name(version_number, Utc::now().date_naive())
+    }
+
+    fn split(version_number: &str) -> Option<Vec<u64>> {
+        let numbers = Self::numbers_of(version_number)?;
+        if numbers.len() != 3 {
+            return None;
+        }
+        Some(numbers)
+    }
+
+    /// All the numbers of the version number, calendar versions may have more than three
+    fn numbers_of(version_number: &str) -> Option<Vec<u64>> {
+        version_number
+            .split('.')
+            .map(|item| item.parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>()
+            .ok()
+    }
+
+    fn join(numbers: &[u64]) -> String {
+        numbers
+            .iter()
+            .map(|number| number.to_string())
+            .collect::<Vec<String>>()
+            .join(".")
+    }
+}
+
+/// The format of release tag names like `v{version}.RELEASE.{date}`, `{version}` is replaced by the version number
+/// and `{date}` by the release date like `20230101`
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Deserialize)]
+#[serde(try_from = "String")]
+pub struct TagFormat {
+    format: String,
+    regex: Regex,
+}
+
+impl Default for TagFormat {
+    fn default() -> TagFormat {
+        TagFormat::try_from(String::from("v{version}.RELEASE.{date}")).unwrap()
+    }
+}
+
+impl TryFrom<String> for TagFormat {
+    type Error = String;
+
+    fn try_from(format: String) -> Result<TagFormat, String> {
+        let valid = format.matches(VERSION_PLACEHOLDER).count() == 1
+            && format.matches(DATE_PLACEHOLDER).count() <= 1
+            && !format.contains(char::is_whitespace);
+        if !valid {
+            return Err(tr!(InvalidTagFormat, format));
+        }
+        let pattern = format.split(VERSION_PLACEHOLDER)
+            .map(|part| part.split(DATE_PLACEHOLDER).map(regex::escape).collect::<Vec<String>>().join("[0-9]{8}"))
+            .collect::<Vec<String>>()
+            .join("(?P<version>[0-9]+(?:\\.[0-9]+)*)");
+        let regex = Regex::new(&format!("^{}$", pattern)).map_err(|_| tr!(InvalidTagFormat, format))?;
+        Ok(TagFormat { format, regex })
+    }
+}
+
+impl TagFormat {
+    /// The tag name of the version number released on the date
+    pub fn tag_name(&self, version_number: &str, date: NaiveDate) -> String {
+        self.format
+            .replace(VERSION_PLACEHOLDER, version_number)
+            .replace(DATE_PLACEHOLDER, &date.format("%Y%m%d").to_string())
+    }
+
+    /// The version number of the release tag, none if the tag is not of the format
+    pub fn version_number_of(&self, tag_name: &str) -> Option<String> {
+        self.regex.captures(tag_name)
+            .and_then(|captures| captures.name("version"))
+            .map(|version| String::from(version.as_str()))
+    }
+
+    /// The tag name of the release candidate, the candidate version behind the part of the format before `{version}`
+    /// without the date, `1.3.0-RC.2` to `v1.3.0-RC.2` by the default format
+    pub fn candidate_tag_name(&self, candidate_version: &str) -> String {
+        let prefix = self.format.split(VERSION_PLACEHOLDER).next().unwrap_or_default();
+        prefix.replace(DATE_PLACEHOLDER, "") + candidate_version
+    }
+
+    /// The numbers of the version's release candidates tagged, from the highest to the lowest
+    pub fn candidate_numbers(&self, version_number: &str, identifier: &str, tag_names: &[String]) -> Vec<u64> {
+        let prefix = self.candidate_tag_name(&format!("{}-{}.", version_number, identifier));
+        let mut candidate_numbers = tag_names.iter()
+            .filter_map(|tag_name| tag_name.strip_prefix(&prefix))
+            .filter_map(|candidate_number| candidate_number.parse::<u64>().ok())
+            .collect::<Vec<u64>>();
+        candidate_numbers.sort_by(|left, right| right.cmp(left));
+        candidate_numbers
+    }
+}
+
+/// Uses the tag format of the config for every release tag, the default one if it is never set
+pub fn init_tag_format(tag_format: TagFormat) {
+    TAG_FORMAT.get_or_init(|| tag_format);
+}
+
+fn tag_format() -> &'static TagFormat {
+    TAG_FORMAT.get_or_init(TagFormat::default)
+}
+
+/// The calendar version pattern like `YYYY.0M.SEQ`, the tokens of <https://calver.org> separated by dots
+/// and ended by the release sequence within the period
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Deserialize)]
+#[serde(try_from = "String")]
+pub struct CalendarPattern {
+    tokens: Vec<String>,
+}
+
+impl Default for CalendarPattern {
+    fn default() -> CalendarPattern {
+        CalendarPattern::try_from(String::from("YYYY.MM.SEQ")).unwrap()
+    }
+}
+
+impl TryFrom<String> for CalendarPattern {
+    type Error = String;
+
+    fn try_from(pattern: String) -> Result<CalendarPattern, String> {
+        let tokens = pattern.split('.').map(String::from).collect::<Vec<String>>();
+        let (last_token, date_tokens) = tokens.split_last().ok_or(tr!(InvalidCalendarPattern, pattern))?;
+        let valid = last_token == SEQUENCE_TOKEN
+            && !date_tokens.is_empty()
+            && date_tokens.iter().all(|token| format_date_token(token, NaiveDate::default()).is_some());
+        if !valid {
+            return Err(tr!(InvalidCalendarPattern, pattern));
+        }
+        Ok(CalendarPattern { tokens })
+    }
+}
+
+impl CalendarPattern {
+    /// The version number released on the date, `2026.10.1` on 2026-10-19 after `2026.10.0`,
+    /// the sequence restarts from 0 when the period of the last release is over
+    /// the hotfix counter of the last version is dropped, `2026.10.1` after the hotfix `2026.10.0.1`
+    pub fn next_version_number(&self, date: NaiveDate, last_version_number: Option<&str>) -> String {
+        let period = self.tokens[..self.tokens.len() - 1]
+            .iter()
+            .filter_map(|token| format_date_token(token, date))
+            .collect::<Vec<String>>()
+            .join(".");
+        let sequence = last_version_number
+            .and_then(|version_number| self.release_version_number_of(version_number))
+            .as_deref()
+            .and_then(|version_number| version_number.rsplit_once('.'))
+            .filter(|(last_period, _)| *last_period == period)
+            .and_then(|(_, last_sequence)| last_sequence.parse::<u64>().ok())
+            .map(|last_sequence| last_sequence + 1)
+            .unwrap_or(0);
+        format!("{}.{}", period, sequence)
+    }
+
+    /// The hotfix of the last version counted after the release sequence, `2026.10.0.1` after `2026.10.0`
+    /// and `2026.10.0.2` after `2026.10.0.1`, so it never takes the version of the next release
+    pub fn next_hotfix_version_number(&self, last_version_number: &str) -> Option<String> {
+        let mut numbers = VersionUtil::numbers_of(last_version_number)?;
+        if numbers.len() == self.tokens.len() {
+            numbers.push(1);
+        } else if numbers.len() == self.tokens.len() + 1 {
+            *numbers.last_mut()? += 1;
+        } else {
+            return None;
+        }
+        let hotfix_number = numbers.last()?.to_string();
+        Some(self.release_version_number_of(last_version_number)? + "." + hotfix_number.as_str())
+    }
+
+    /// The next hotfix of the release line after the hotfixes tagged, `2026.10.0.3` of `2026.10.0`
+    /// when `2026.10.0.2` is released
+    pub fn next_hotfix_in_line(&self, line: &str, tag_names: &[String]) -> Option<String> {
+        let line_numbers = VersionUtil::numbers_of(line).filter(|numbers| numbers.len() == self.tokens.len())?;
+        let latest_hotfix = tag_names.iter()
+            .filter_map(|tag_name| VersionUtil::version_number_of_tag(tag_name))
+            .filter_map(|version_number| VersionUtil::numbers_of(&version_number))
+            .filter(|numbers| numbers.len() == self.tokens.len() + 1 && numbers[..self.tokens.len()] == line_numbers[..])
+            .filter_map(|numbers| numbers.last().copied())
+            .fold(0, u64::max);
+        Some(format!("{}.{}", line, latest_hotfix + 1))
+    }
+
+    /// The oldest of the version numbers released after the last version number, `2026.11.0` of
+    /// `2026.11.0` and `2026.12.0` after `2026.10.0.1`, the ones not of the pattern are skipped
+    pub fn first_release_after(&self, version_numbers: &[String], last_version_number: Option<&str>) -> Option<String> {
+        let last_numbers = last_version_number.and_then(VersionUtil::numbers_of).unwrap_or_default();
+        version_numbers.iter()
+            .filter_map(|version_number| Some((VersionUtil::numbers_of(version_number)?, version_number)))
+            .filter(|(numbers, _)| numbers.len() == self.tokens.len() && *numbers > last_numbers)
+            .min()
+            .map(|(_, version_number)| version_number.clone())
+    }
+
+    /// The release part of the version number without the hotfix counter, `2026.10.0` of `2026.10.0.1`
+    pub fn release_version_number_of(&self, version_number: &str) -> Option<String> {
+        let parts = version_number.split('.').collect::<Vec<&str>>();
+        if parts.len() < self.tokens.len() {
+            return None;
+        }
+        Some(parts[..self.tokens.len()].join("."))
+    }
+}
+
+fn format_date_token(token: &str, date: NaiveDate) -> Option<String> {
+    let formatted = match token {
+        "YYYY" => date.year().to_string(),
+        "YY" => (date.year() % 100).to_string(),
+        "0Y" => format!("{:02}", date.year() % 100),
+        "MM" => date.month().to_string(),
+        "0M" => format!("{:02}", date.month()),
+        "WW" => date.iso_week().week().to_string(),
+        "0W" => format!("{:02}", date.iso_week().week()),
+        "DD" => date.day().to_string(),
+        "0D" => format!("{:02}", date.day()),
+        _ => return None,
+    };
+    Some(formatted)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::support::version::{CalendarPattern, TagFormat, VersionUtil};
+
+    #[test]
+    fn version_number_of_tag() {
+        assert_eq!(Some(String::from("1.2.0")), VersionUtil::version_number_of_tag("v1.2.0.RELEASE.20230101"));
+        assert_eq!(None, VersionUtil::version_number_of_tag("1.2.0"));
+        assert!(VersionUtil::is_version_number("1.3"));
+        assert!(!VersionUtil::is_version_number("1.3.0-SNAPSHOT"));
+        assert!(!VersionUtil::is_version_number("login-page"));
+    }
+
+    #[test]
+    fn tag_format() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        let tag_format = TagFormat::default();
+        assert_eq!("v1.3.0.RELEASE.20261019", tag_format.tag_name("1.3.0", date));
+        assert_eq!(None, tag_format.version_number_of("v1.3.0-RC.1"));
+        assert_eq!(None, tag_format.version_number_of("v1.3.0.RELEASE"));
+        let tag_format = TagFormat::try_from(String::from("release-{version}")).unwrap();
+        assert_eq!("release-2026.10.0.1", tag_format.tag_name("2026.10.0.1", date));
+        assert_eq!(Some(String::from("2026.10.0.1")), tag_format.version_number_of("release-2026.10.0.1"));
+        assert_eq!(None, tag_format.version_number_of("v1.3.0.RELEASE.20261019"));
+        assert!(TagFormat::try_from(String::from("v{date}")).is_err());
+        assert!(TagFormat::try_from(String::from("{version}-{version}")).is_err());
+    }
+
+    #[test]
+    fn is_development_version() {
+        assert!(VersionUtil::is_development_version("1.3.0-SNAPSHOT"));
+        assert!(VersionUtil::is_development_version("1.3.0-beta.1"));
+        assert!(!VersionUtil::is_development_version("1.2.0"));
+        assert!(!VersionUtil::is_development_version("1.2.0.RELEASE"));
+    }
+
+    #[test]
+    fn next_versions() {
+        assert_eq!(Some(String::from("1.3.0")), VersionUtil::next_minor("1.2.3"));
+        assert_eq!(Some(String::from("1.2.4")), VersionUtil::next_patch("1.2.3"));
+        assert_eq!(Some(String::from("2.0.0")), VersionUtil::next_major("1.2.3"));
+        assert_eq!(String::from("1.0.0"), VersionUtil::next_major_release_version_number(None));
+        assert_eq!(None, VersionUtil::next_patch("1.2"));
+        assert_eq!(String::from("1.0.0"), VersionUtil::next_release_test_version_number(None));
+        assert_eq!(None, VersionUtil::next_hotfix_version_number(None));
+    }
+
+    #[test]
+    fn next_patch_in_line() {
+        let tag_names = vec![
+            String::from("v1.3.4.RELEASE.20230101"),
+            String::from("v1.3.5.RELEASE.20230201"),
+            String::from("v1.4.0.RELEASE.20230301"),
+            String::from("latest"),
+        ];
+        assert_eq!(Some(String::from("1.3.6")), VersionUtil::next_patch_in_line("1.3.4", &tag_names));
+        assert_eq!(Some(String::from("1.4.1")), VersionUtil::next_patch_in_line("1.4.0", &tag_names));
+        assert_eq!(Some(String::from("1.2.1")), VersionUtil::next_patch_in_line("1.2.0", &tag_names));
+        assert_eq!(Some(String::from("1.3")), VersionUtil::minor_line_of("1.3.4"));
+        assert_eq!(
+            vec!["v1.4.0.RELEASE.20230301", "v1.3.5.RELEASE.20230201", "v1.3.4.RELEASE.20230101"],
+            VersionUtil::sort_release_tags(tag_names)
+        );
+    }
+
+    #[test]
+    fn candidate_numbers() {
+        let tag_names = vec![
+            String::from("v1.3.0-RC.1"),
+            String::from("v1.3.0-RC.10"),
+            String::from("v1.3.0-RC.2"),
+            String::from("v1.3.1-RC.3"),
+            String::from("v1.3.0-rc.4"),
+            String::from("v1.3.0.RELEASE.20230101"),
+        ];
+        assert_eq!(vec![10, 2, 1], VersionUtil::candidate_numbers("1.3.0", "RC", &tag_names));
+        assert_eq!("v1.3.0-RC.11", VersionUtil::candidate_tag_name(&VersionUtil::candidate_version("1.3.0", "RC", 11)));
+        assert_eq!(None, VersionUtil::version_number_of_tag("v1.3.0-RC.1"));
+        let tag_format = TagFormat::try_from(String::from("release-{version}-{date}")).unwrap();
+        let tag_names = vec![
+            String::from("release-1.3.0-RC.1"),
+            String::from("release-1.3.0-RC.2"),
+            String::from("v1.3.0-RC.3"),
+        ];
+        assert_eq!(vec![2, 1], tag_format.candidate_numbers("1.3.0", "RC", &tag_names));
+        assert_eq!("release-1.3.0-RC.3", tag_format.candidate_tag_name(&VersionUtil::candidate_version("1.3.0", "RC", 3)));
+        assert_eq!(None, tag_format.version_number_of("release-1.3.0-RC.1"));
+    }
+
+    #[test]
+    fn next_calendar_version_number() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        let pattern = CalendarPattern::default();
+        assert_eq!("2026.10.0", pattern.next_version_number(date, None));
+        assert_eq!("2026.10.0", pattern.next_version_number(date, Some("2026.9.3")));
+        assert_eq!("2026.10.2", pattern.next_version_number(date, Some("2026.10.1")));
+        assert_eq!("2026.10.2", pattern.next_version_number(date, Some("2026.10.1.3")));
+        assert_eq!(Some(String::from("2026.10.1.1")), pattern.next_hotfix_version_number("2026.10.1"));
+        assert_eq!(Some(String::from("2026.10.1.2")), pattern.next_hotfix_version_number("2026.10.1.1"));
+        assert_eq!(None, pattern.next_hotfix_version_number("2026.10"));
+        let tag_names = vec![String::from("v2026.10.0.RELEASE.20261001"), String::from("v2026.10.0.2.RELEASE.20261005"), String::from("v2026.10.1.RELEASE.20261010")];
+        assert_eq!(Some(String::from("2026.10.0.3")), pattern.next_hotfix_in_line("2026.10.0", &tag_names));
+        assert_eq!(Some(String::from("2026.10.1.1")), pattern.next_hotfix_in_line("2026.10.1", &tag_names));
+        assert_eq!(None, pattern.next_hotfix_in_line("2026.10.0.2", &tag_names));
+        let version_numbers = vec![String::from("2026.12.0"), String::from("2026.10.1"), String::from("2026.11.0"), String::from("login")];
+        assert_eq!(Some(String::from("2026.11.0")), pattern.first_release_after(&version_numbers, Some("2026.10.1.1")));
+        assert_eq!(Some(String::from("2026.10.1")), pattern.first_release_after(&version_numbers, None));
+        assert_eq!(None, pattern.first_release_after(&version_numbers, Some("2026.12.0")));
+        let pattern = CalendarPattern::try_from(String::from("0Y.0M.0D.SEQ")).unwrap();
+        assert_eq!("26.10.19.0", pattern.next_version_number(date, Some("26.10.18.0")));
+        assert!(CalendarPattern::try_from(String::from("YYYY.MM")).is_err());
+        assert!(CalendarPattern::try_from(String::from("YYYY.SEQ.MM")).is_err());
+        assert!(CalendarPattern::try_from(String::from("SEQ")).is_err());
+        assert!(CalendarPattern::try_from(String::from("YYYY.month.SEQ")).is_err());
+    }
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use chrono::Utc;
+use common::{release_tag_of, Project, TestRepository};
+
+const CALVER_TAG: &str = "v2020.1.0.RELEASE.20200115";
+
+/// Released 2020.1.0 with CalVer, develop moved to 2020.1.1 and its test branch created in January 2020
+fn calendar_released_repository() -> TestRepository {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.commit_file(".gitflow.toml", "[release]\nversion-scheme = \"calver\"\n");
+    repository.commit_version("release: 2020.1.0", "2020.1.0.RELEASE");
+    repository.git(&["tag", CALVER_TAG]);
+    repository.commit_version("back to : 2020.1.1-SNAPSHOT", "2020.1.1-SNAPSHOT");
+    repository.git(&["branch", "--force", "develop", "master"]);
+    repository.git(&["switch", "-c", "test/2020.1.1", "develop"]);
+    repository.commit_file("feature.txt", "some feature");
+    repository.git(&["push", "--force", "--set-upstream", "origin", "master", "develop", "test/2020.1.1"]);
+    repository.git(&["push", "--tags"]);
+    repository.git(&["switch", "master"]);
+    repository
+}
+
+#[test]
+fn calendar_hotfix_and_release_keep_apart() {
+    let repository = calendar_released_repository();
+    repository.run_flow(&["--flow-type", "Hotfix"]);
+    assert_eq!("hotfix/2020.1.0.1", repository.current_branch());
+    assert_eq!("2020.1.0.1-SNAPSHOT", repository.working_version());
+    repository.commit_file("fix.txt", "some fix");
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Hotfix"]);
+    assert_eq!("2020.1.0.1.RELEASE", repository.version_at(&release_tag_of("2020.1.0.1")));
+
+    // the month of the test branch is over, the release keeps its version
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test"]);
+    let tag = release_tag_of("2020.1.1");
+    assert_eq!("2020.1.1.RELEASE", repository.version_at(&tag));
+    assert_eq!("some feature", repository.git(&["show", format!("{}:feature.txt", tag).as_str()]));
+    assert_eq!("some fix", repository.git(&["show", format!("{}:fix.txt", tag).as_str()]));
+    let next_version_number = Utc::now().format("%Y.%-m.0").to_string();
+    assert_eq!(format!("feature/{}", next_version_number), repository.current_branch());
+    assert_eq!(next_version_number + "-SNAPSHOT", repository.version_at("develop"));
+}
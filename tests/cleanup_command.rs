@@ -0,0 +1,56 @@
+mod common;
+
+use common::{Project, TestRepository};
+
+/// hotfix/1.2.1 released and merged into master, feature/1.1.0 only pushed, feature/1.4.0 not released
+fn repository_with_flow_branches() -> TestRepository {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.run_flow(&["--flow-type", "Hotfix"]);
+    repository.git(&["push", "--set-upstream", "origin", "hotfix/1.2.1"]);
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Hotfix"]);
+    repository.git(&["switch", "-c", "feature/1.1.0", "develop"]);
+    repository.commit_file("old.txt", "old feature");
+    repository.git(&["push", "origin", "feature/1.1.0"]);
+    repository.git(&["switch", "-c", "feature/1.4.0", "develop"]);
+    repository.git(&["branch", "-D", "feature/1.1.0"]);
+    repository.commit_file("new.txt", "new feature");
+    repository.git(&["push", "--set-upstream", "origin", "feature/1.4.0"]);
+    repository
+}
+
+fn remote_branches(repository: &TestRepository) -> Vec<String> {
+    repository.git(&["ls-remote", "--heads", "origin"])
+        .lines()
+        .filter_map(|line| line.split("refs/heads/").nth(1))
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn cleanup_dry_run_deletes_nothing() {
+    let repository = repository_with_flow_branches();
+    let output = repository.run(&["cleanup", "--dry-run"]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("git branch -D hotfix/1.2.1"), "{}", stderr);
+    assert!(stderr.contains("git push origin --delete hotfix/1.2.1"), "{}", stderr);
+    assert!(stderr.contains("git push origin --delete feature/1.1.0"), "{}", stderr);
+    assert!(!stderr.contains("feature/1.4.0"), "{}", stderr);
+    assert!(repository.branches().contains(&String::from("hotfix/1.2.1")));
+    assert!(remote_branches(&repository).contains(&String::from("feature/1.1.0")));
+}
+
+#[test]
+fn cleanup_deletes_released_branches_locally_and_remotely() {
+    let repository = repository_with_flow_branches();
+    let output = repository.run(&["cleanup", "--yes"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let branches = repository.branches();
+    assert!(!branches.contains(&String::from("hotfix/1.2.1")));
+    assert!(branches.contains(&String::from("feature/1.4.0")));
+    let remote_branches = remote_branches(&repository);
+    assert!(!remote_branches.contains(&String::from("hotfix/1.2.1")));
+    assert!(!remote_branches.contains(&String::from("feature/1.1.0")));
+    assert!(remote_branches.contains(&String::from("feature/1.4.0")));
+    assert!(remote_branches.contains(&String::from("develop")));
+}
@@ -0,0 +1,251 @@
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use chrono::Utc;
+use regex::Regex;
+use tempfile::TempDir;
+
+/// The release tag seeded on master
+pub const SEED_TAG: &str = "v1.2.0.RELEASE.20240101";
+
+/// Keeps the user's global and system git configuration out of the tests
+const ISOLATED_GIT_ENVS: [(&str, &str); 2] = [("GIT_CONFIG_GLOBAL", "/dev/null"), ("GIT_CONFIG_NOSYSTEM", "1")];
+
+const PACKAGE_JSON: &str = r#"{
+  "name": "explore-webpack",
+  "version": "{version}",
+  "private": true,
+  "scripts": {
+    "build": "echo build"
+  }
+}
+"#;
+
+const POM_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0"
+         xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+         xsi:schemaLocation="http://maven.apache.org/POM/4.0.0 http://maven.apache.org/xsd/maven-4.0.0.xsd">
+    <modelVersion>4.0.0</modelVersion>
+    <groupId>com.example</groupId>
+    <artifactId>explore-maven</artifactId>
+    <version>{version}</version>
+    <packaging>jar</packaging>
+</project>
+"#;
+
+const CHART_YAML: &str = r#"# deployed by the release pipeline
+apiVersion: v2
+name: explore-helm
+version: {version} # moved by the flow
+appVersion: "{version}"
+"#;
+
+#[derive(Clone, Copy)]
+pub enum Project {
+    Maven,
+    Webpack,
+    Helm,
+}
+
+impl Project {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Project::Maven => "Maven",
+            Project::Webpack => "Webpack",
+            Project::Helm => "Helm",
+        }
+    }
+
+    fn file_name(&self) -> &'static str {
+        match self {
+            Project::Maven => "pom.xml",
+            Project::Webpack => "package.json",
+            Project::Helm => "Chart.yaml",
+        }
+    }
+
+    fn render(&self, version: &str) -> String {
+        let template = match self {
+            Project::Maven => POM_XML,
+            Project::Webpack => PACKAGE_JSON,
+            Project::Helm => CHART_YAML,
+        };
+        match self {
+            // charts hold SemVer versions, the release version is the plain version number
+            Project::Helm => template.replace("{version}", version.trim_end_matches(".RELEASE")),
+            _ => template.replace("{version}", version),
+        }
+    }
+
+    fn extract_version(&self, content: &str) -> Option<String> {
+        let regex = match self {
+            Project::Maven => Regex::new(r"</artifactId>\s*<version>([^<]+)</version>").unwrap(),
+            Project::Webpack => Regex::new(r#""version"\s*:\s*"([^"]+)""#).unwrap(),
+            Project::Helm => Regex::new(r"(?m)^version: (\S+)").unwrap(),
+        };
+        regex.captures(content).map(|captures| String::from(&captures[1]))
+    }
+}
+
+/// A working clone of a temporary bare `origin`, seeded with
+/// master released as 1.2.0 and develop at 1.3.0-SNAPSHOT
+pub struct TestRepository {
+    _temp_dir: TempDir,
+    pub project: Project,
+    pub origin: PathBuf,
+    pub work: PathBuf,
+}
+
+impl TestRepository {
+    pub fn new(project: Project) -> TestRepository {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let origin = temp_dir.path().join("origin.git");
+        let work = temp_dir.path().join("work");
+        run_git(temp_dir.path(), &["init", "--bare", "--initial-branch=master", "origin.git"]);
+        run_git(temp_dir.path(), &["clone", "origin.git", "work"]);
+        let repository = TestRepository {
+            _temp_dir: temp_dir,
+            project,
+            origin,
+            work,
+        };
+        repository.git(&["config", "user.name", "Explore Tester"]);
+        repository.git(&["config", "user.email", "tester@example.com"]);
+        repository.git(&["symbolic-ref", "HEAD", "refs/heads/master"]);
+        repository.commit_version("release: 1.2.0", "1.2.0.RELEASE");
+        repository.git(&["tag", SEED_TAG]);
+        repository.commit_version("back to : 1.3.0-SNAPSHOT", "1.3.0-SNAPSHOT");
+        repository.git(&["branch", "develop"]);
+        repository.git(&["push", "--set-upstream", "origin", "master", "develop"]);
+        repository.git(&["push", "--tags"]);
+        repository
+    }
+
+    /// Runs git in the working clone and returns the trimmed stdout
+    pub fn git(&self, args: &[&str]) -> String {
+        run_git(&self.work, args)
+    }
+
+    /// Writes the project version file and commits it on the current branch
+    pub fn commit_version(&self, message: &str, version: &str) {
+        fs::write(self.work.join(self.project.file_name()), self.project.render(version)).unwrap();
+        self.git(&["add", "."]);
+        self.git(&["commit", "-m", message]);
+    }
+
+    /// Commits a new file on the current branch
+    pub fn commit_file(&self, file_name: &str, message: &str) {
+        fs::write(self.work.join(file_name), message).unwrap();
+        self.git(&["add", "."]);
+        self.git(&["commit", "-m", message]);
+    }
+
+    /// Runs the binary in the working clone without any prompt
+    pub fn run(&self, args: &[&str]) -> Output {
+        self.run_with_envs(args, &[])
+    }
+
+    /// Runs the binary in the working clone with the environment variables, stdin is not a terminal
+    pub fn run_with_envs(&self, args: &[&str], envs: &[(&str, &str)]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_command-line-tool-explore"))
+            .envs(ISOLATED_GIT_ENVS)
+            .envs(envs.iter().copied())
+            // messages are asserted in English unless `--lang` is given
+            .env("LANG", "en_US.UTF-8")
+            .env_remove("LC_ALL")
+            .env_remove("LC_MESSAGES")
+            .current_dir(&self.work)
+            .args(args)
+            .output()
+            .unwrap()
+    }
+
+    /// Runs a flow and asserts it succeeded
+    pub fn run_flow(&self, args: &[&str]) -> Output {
+        let mut all_args = vec!["--project-type", self.project.name(), "--yes"];
+        all_args.extend_from_slice(args);
+        let output = self.run(&all_args);
+        assert!(
+            output.status.success(),
+            "flow {:?} failed :\n{}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        output
+    }
+
+    pub fn current_branch(&self) -> String {
+        self.git(&["branch", "--show-current"])
+    }
+
+    pub fn branches(&self) -> Vec<String> {
+        self.git(&["branch", "--format=%(refname:short)"])
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    pub fn tags(&self) -> Vec<String> {
+        self.git(&["tag", "--list"]).lines().map(String::from).collect()
+    }
+
+    /// The project version stored at the revision
+    pub fn version_at(&self, revision: &str) -> String {
+        let content = self.git(&["show", format!("{}:{}", revision, self.project.file_name()).as_str()]);
+        self.project.extract_version(&content).unwrap()
+    }
+
+    /// The project version in the working tree
+    pub fn working_version(&self) -> String {
+        let content = fs::read_to_string(self.work.join(self.project.file_name())).unwrap();
+        self.project.extract_version(&content).unwrap()
+    }
+
+    pub fn commit_message(&self, revision: &str) -> String {
+        self.git(&["log", "-1", "--format=%s", revision])
+    }
+
+    pub fn rev_parse(&self, revision: &str) -> String {
+        self.git(&["rev-parse", format!("{}^{{commit}}", revision).as_str()])
+    }
+
+    pub fn is_ancestor(&self, ancestor: &str, descendant: &str) -> bool {
+        Command::new("git")
+            .envs(ISOLATED_GIT_ENVS)
+            .current_dir(&self.work)
+            .args(["merge-base", "--is-ancestor", ancestor, descendant])
+            .status()
+            .unwrap()
+            .success()
+    }
+}
+
+/// The tag created today for the release version
+pub fn release_tag_of(version: &str) -> String {
+    format!("v{}.RELEASE.{}", version, Utc::now().format("%Y%m%d"))
+}
+
+/// Fails the test when the build tool it runs is not installed, instead of passing without running
+pub fn require_command(command: &str) {
+    let available = Command::new(command).arg("--version").output().is_ok();
+    assert!(available, "<{}> is required by this test but is not installed", command);
+}
+
+fn run_git(directory: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .envs(ISOLATED_GIT_ENVS)
+        .current_dir(directory)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {:?} failed :\n{}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from(String::from_utf8_lossy(&output.stdout).trim())
+}
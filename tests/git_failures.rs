@@ -0,0 +1,54 @@
+mod common;
+
+use std::fs;
+
+use common::{Project, TestRepository};
+
+/// develop has a file which is untracked on master
+fn untracked_file_in_the_way() -> TestRepository {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "develop"]);
+    repository.commit_file("notes.md", "notes of develop");
+    repository.git(&["switch", "master"]);
+    fs::write(repository.work.join("notes.md"), "local notes").unwrap();
+    repository
+}
+
+#[test]
+fn untracked_file_in_the_way_suggests_stash() {
+    let repository = untracked_file_in_the_way();
+    let output = repository.run(&["--project-type", "Webpack", "--yes", "--flow-type", "Feature"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Git command failed : git switch develop , Exit code : 1"), "{}", stderr);
+    assert!(stderr.contains("would be overwritten by checkout"), "{}", stderr);
+    assert!(stderr.contains("There are uncommitted changes the command would overwrite"), "{}", stderr);
+    assert!(stderr.contains("Suggested fix : git stash push --include-untracked"), "{}", stderr);
+}
+
+#[test]
+fn untracked_file_in_the_way_suggests_stash_with_libgit2() {
+    let repository = untracked_file_in_the_way();
+    let output = repository.run(&["--project-type", "Webpack", "--yes", "--git-backend", "Libgit2", "--flow-type", "Feature"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("There are uncommitted changes the command would overwrite"), "{}", stderr);
+    assert!(stderr.contains("Suggested fix : git stash push --include-untracked"), "{}", stderr);
+}
+
+#[test]
+fn diverged_develop_suggests_rebase() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "develop"]);
+    repository.commit_file("notes.md", "notes pushed by others");
+    repository.git(&["push"]);
+    repository.git(&["reset", "--hard", "HEAD~1"]);
+    repository.commit_file("other.md", "notes not pushed yet");
+
+    let output = repository.run(&["--project-type", "Webpack", "--yes", "--flow-type", "Feature"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Git command failed : git pull , Exit code : 128"), "{}", stderr);
+    assert!(stderr.contains("The remote branch has commits the local branch does not have"), "{}", stderr);
+    assert!(stderr.contains("Suggested fix : git pull --rebase"), "{}", stderr);
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use std::fs;
+
+use common::{release_tag_of, Project, TestRepository};
+
+const SUBCHART: &str = "apiVersion: v2\nname: worker\nversion: 1.3.0-SNAPSHOT\n";
+
+const DEPENDENCY: &str = "apiVersion: v2\nname: redis\nversion: 7.0.5\nappVersion: \"7.0.5\"\n";
+
+#[test]
+fn release_test_flow_moves_chart_and_subchart_versions() {
+    let repository = TestRepository::new(Project::Helm);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    for (name, content) in [("worker", SUBCHART), ("redis", DEPENDENCY)] {
+        fs::create_dir_all(repository.work.join("charts").join(name)).unwrap();
+        fs::write(repository.work.join("charts").join(name).join("Chart.yaml"), content).unwrap();
+    }
+    repository.commit_file("values.yaml", "replicaCount: 1\n");
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test"]);
+
+    let tag_name = release_tag_of("1.3.0");
+    let chart = repository.git(&["show", &format!("{}:Chart.yaml", tag_name)]);
+    assert!(chart.starts_with("# deployed by the release pipeline\n"), "{}", chart);
+    assert!(chart.contains("version: 1.3.0 # moved by the flow\nappVersion: \"1.3.0\""), "{}", chart);
+    assert_eq!(SUBCHART.trim_end().replace("1.3.0-SNAPSHOT", "1.3.0"), repository.git(&["show", &format!("{}:charts/worker/Chart.yaml", tag_name)]));
+    assert_eq!(DEPENDENCY.trim_end(), repository.git(&["show", &format!("{}:charts/redis/Chart.yaml", tag_name)]));
+    assert_eq!("1.4.0-SNAPSHOT", repository.version_at("develop"));
+    assert_eq!(SUBCHART.trim_end().replace("1.3.0-SNAPSHOT", "1.4.0-SNAPSHOT"), repository.git(&["show", "develop:charts/worker/Chart.yaml"]));
+    let output = repository.run(&["--project-type", "Helm", "verify-versions"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn invalid_chart_fails_verification() {
+    let repository = TestRepository::new(Project::Helm);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file("values.yaml", "image: [");
+    let master = repository.rev_parse("master");
+
+    let output = repository.run(&["--project-type", "Helm", "--yes", "--flow-type", "Release", "--release-type", "Test"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Can't parse YAML : ./values.yaml"), "{}", stderr);
+    assert!(!repository.tags().contains(&release_tag_of("1.3.0")));
+    assert_eq!(master, repository.rev_parse("master"));
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use std::fs;
+
+use common::{release_tag_of, Project, TestRepository};
+use serde_json::Value;
+
+fn audit_records(repository: &TestRepository) -> Vec<Value> {
+    fs::read_to_string(repository.work.join(".git").join("gitflow-audit.jsonl"))
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn flows_are_appended_to_audit_log() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file("feature.txt", "some feature");
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test"]);
+    let records = audit_records(&repository);
+    assert_eq!(1, records.len());
+    let record = &records[0];
+    assert_eq!("Explore Tester <tester@example.com>", record["user"]);
+    assert_eq!("Release", record["parameters"]["flow_type"]);
+    assert_eq!("Test", record["parameters"]["release_type"]);
+    assert_eq!("test/1.3.0", record["original_branch"]);
+    assert_eq!("success", record["outcome"]);
+    let tag_ref = format!("refs/tags/{}", release_tag_of("1.3.0"));
+    assert!(record["refs_before"].get(&tag_ref).is_none());
+    assert_eq!(repository.rev_parse(&tag_ref), record["refs_after"][&tag_ref]);
+    let tag_command = format!("git tag {}", release_tag_of("1.3.0"));
+    let commands = record["commands"].as_array().unwrap();
+    assert!(commands.iter().any(|command| command["command"] == tag_command.as_str() && command["exit_code"] == 0));
+}
+
+#[test]
+fn history_prints_executions_and_failures() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file("feature.txt", "some feature");
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test"]);
+    let output = repository.run(&["--project-type", "Webpack", "--yes", "--flow-type", "Release", "--release-type", "Hotfix"]);
+    assert!(!output.status.success());
+
+    let output = repository.run(&["history"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Release(Test)"), "{}", stdout);
+    assert!(stdout.contains(&format!("+tags/{}", release_tag_of("1.3.0"))), "{}", stdout);
+    assert!(stdout.contains("+feature/1.4.0"), "{}", stdout);
+    assert!(stdout.contains("Release(Hotfix)"), "{}", stdout);
+    assert!(stdout.contains("failure : Hotfix branch doesn't exists"), "{}", stdout);
+    let records = audit_records(&repository);
+    assert_eq!("failure", records[1]["outcome"]);
+}
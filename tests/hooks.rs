@@ -0,0 +1,47 @@
+mod common;
+
+use std::fs;
+
+use common::{release_tag_of, Project, TestRepository};
+
+const LOG_HOOK: &str = r#"echo \"$GITFLOW_HOOK|$GITFLOW_FLOW_TYPE|$GITFLOW_RELEASE_TYPE|$GITFLOW_OLD_VERSION|$GITFLOW_NEW_VERSION|$GITFLOW_BRANCH|$GITFLOW_TAG\" >> ../hooks.log"#;
+
+#[test]
+fn hooks_receive_flow_environment() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    let config = ["pre-flow", "post-version-change", "pre-release-commit", "post-tag", "post-flow"]
+        .iter()
+        .map(|hook| format!("{} = [\"{}\"]\n", hook, LOG_HOOK))
+        .collect::<String>();
+    repository.commit_file(".gitflow.toml", &format!("[hooks]\n{}", config));
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test"]);
+
+    let tag = release_tag_of("1.3.0");
+    let expected = [
+        String::from("pre-flow|Release|Test||||"),
+        String::from("post-version-change|Release|Test|1.3.0-SNAPSHOT|1.3.0.RELEASE|test/1.3.0|"),
+        String::from("pre-release-commit|Release|Test|1.3.0-SNAPSHOT|1.3.0.RELEASE|test/1.3.0|"),
+        format!("post-tag|Release|Test|1.3.0-SNAPSHOT|1.3.0.RELEASE|test/1.3.0|{}", tag),
+        format!("post-version-change|Release|Test|1.3.0.RELEASE|1.4.0-SNAPSHOT|test/1.3.0|{}", tag),
+        format!("post-flow|Release|Test|1.3.0.RELEASE|1.4.0-SNAPSHOT|test/1.3.0|{}", tag),
+    ];
+    let log = fs::read_to_string(repository.work.join("../hooks.log")).unwrap();
+    assert_eq!(expected.to_vec(), log.lines().collect::<Vec<&str>>());
+}
+
+#[test]
+fn failed_hook_cancels_release() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file(".gitflow.toml", "[hooks]\npre-release-commit = [\"exit 3\"]\n");
+    let master = repository.rev_parse("master");
+
+    let output = repository.run(&["--project-type", "Webpack", "--yes", "--flow-type", "Release", "--release-type", "Test"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("The release is cancelled and master is reset to before the release : Hook failed, the flow is stopped : pre-release-commit : exit 3"), "{}", stderr);
+    assert!(!repository.tags().contains(&release_tag_of("1.3.0")));
+    assert_eq!(master, repository.rev_parse("master"));
+    assert_eq!("", repository.git(&["status", "--porcelain", "--untracked-files=no"]));
+}
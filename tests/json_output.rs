@@ -0,0 +1,48 @@
+mod common;
+
+use serde_json::Value;
+
+use common::{release_tag_of, Project, TestRepository};
+
+fn report_of(stdout: &[u8]) -> Value {
+    serde_json::from_slice::<Value>(stdout).unwrap_or_else(|err| panic!("{} :\n{}", err, String::from_utf8_lossy(stdout)))
+}
+
+#[test]
+fn release_test_flow_prints_json_report() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file("feature.txt", "some feature");
+    let output = repository.run_flow(&["--flow-type", "Release", "--release-type", "Test", "--output", "json"]);
+
+    let report = report_of(&output.stdout);
+    let tag_name = release_tag_of("1.3.0");
+    assert_eq!("success", report["outcome"]);
+    assert_eq!("Release", report["flow_type"]);
+    assert_eq!("Test", report["release_type"]);
+    assert_eq!("Webpack", report["project_type"]);
+    assert_eq!("1.3.0-SNAPSHOT", report["previous_version"]);
+    assert_eq!("1.3.0.RELEASE", report["released_version"]);
+    assert_eq!("1.4.0-SNAPSHOT", report["next_development_version"]);
+    assert_eq!(tag_name.as_str(), report["tag"]);
+    assert_eq!(serde_json::json!(["feature/1.4.0"]), report["created_branches"]);
+    assert_eq!(serde_json::json!(["develop", "master"]), report["moved_branches"]);
+    let commits = report["commits"].as_array().unwrap();
+    assert_eq!(2, commits.len());
+    assert_eq!("release: 1.3.0", commits[0]["message"]);
+    assert_eq!(repository.rev_parse(&tag_name).as_str(), commits[0]["id"]);
+    assert_eq!("back to : 1.4.0-SNAPSHOT", commits[1]["message"]);
+}
+
+#[test]
+fn failed_flow_prints_json_report() {
+    let repository = TestRepository::new(Project::Webpack);
+    let output = repository.run(&["--project-type", "Webpack", "--yes", "--flow-type", "Release", "--release-type", "Test", "--output", "json"]);
+    assert!(!output.status.success());
+
+    let report = report_of(&output.stdout);
+    assert_eq!("failure", report["outcome"]);
+    assert!(report["message"].as_str().unwrap().contains("test/1.3.0"), "{}", report);
+    assert_eq!(Value::Null, report["tag"]);
+    assert!(report["commits"].as_array().unwrap().is_empty());
+}
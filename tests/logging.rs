@@ -0,0 +1,41 @@
+mod common;
+
+use std::fs;
+
+use regex::Regex;
+
+use common::{Project, TestRepository};
+
+#[test]
+fn quiet_flow_writes_everything_into_log_file() {
+    let repository = TestRepository::new(Project::Webpack);
+    let log_file = repository.work.join("../flow.log");
+    let output = repository.run_flow(&["-q", "--flow-type", "Hotfix", "--log-file", log_file.to_str().unwrap()]);
+
+    assert_eq!("", String::from_utf8_lossy(&output.stderr));
+    let log = fs::read_to_string(&log_file).unwrap();
+    let command_finished = Regex::new(r"(?m)^\S+ DEBUG Command finished : git fetch , Exit code : 0 , Duration : \d+ ms$").unwrap();
+    assert!(command_finished.is_match(&log), "{}", log);
+    assert!(log.contains(" INFO  Hotfix flow execution completed,Branch name : hotfix/1.2.1"), "{}", log);
+}
+
+#[test]
+fn piped_flow_prints_without_color() {
+    let repository = TestRepository::new(Project::Webpack);
+    let output = repository.run_flow(&["--flow-type", "Hotfix"]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Hotfix flow execution completed,Branch name : hotfix/1.2.1"), "{}", stderr);
+    assert!(!stderr.contains('\u{1b}'), "{}", stderr);
+}
+
+#[test]
+fn verbose_flow_prints_commands_without_color() {
+    let repository = TestRepository::new(Project::Webpack);
+    let output = repository.run_with_envs(&["--project-type", "Webpack", "--yes", "-v", "--flow-type", "Hotfix"], &[("NO_COLOR", "1")]);
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Run command : git branch hotfix/1.2.1"), "{}", stderr);
+    assert!(!stderr.contains('\u{1b}'), "{}", stderr);
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use common::{release_tag_of, Project, TestRepository};
+
+#[test]
+#[ignore = "requires mvn"]
+fn feature_flow_creates_branch_from_develop() {
+    let repository = TestRepository::new(Project::Maven);
+    repository.run_flow(&["--flow-type", "Feature"]);
+    assert_eq!("feature/1.3.0", repository.current_branch());
+    assert_eq!("1.3.0-SNAPSHOT", repository.working_version());
+}
+
+#[test]
+#[ignore = "requires mvn"]
+fn hotfix_flow_creates_branch_from_master() {
+    let repository = TestRepository::new(Project::Maven);
+    repository.run_flow(&["--flow-type", "Hotfix"]);
+    assert_eq!("hotfix/1.2.1", repository.current_branch());
+    assert_eq!("1.2.1-SNAPSHOT", repository.version_at("hotfix/1.2.1"));
+    assert_eq!("new hotfix: 1.2.1", repository.commit_message("hotfix/1.2.1"));
+}
+
+#[test]
+#[ignore = "requires mvn"]
+fn release_test_flow_tags_master_and_starts_next_feature() {
+    let repository = TestRepository::new(Project::Maven);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file("feature.txt", "some feature");
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test"]);
+    let tag_name = release_tag_of("1.3.0");
+    assert_eq!("1.3.0.RELEASE", repository.version_at(&tag_name));
+    assert_eq!("release: 1.3.0", repository.commit_message(&tag_name));
+    assert_eq!("1.4.0-SNAPSHOT", repository.version_at("master"));
+    assert_eq!("1.4.0-SNAPSHOT", repository.version_at("develop"));
+    assert_eq!("feature/1.4.0", repository.current_branch());
+}
@@ -0,0 +1,31 @@
+mod common;
+
+use common::{Project, TestRepository};
+
+#[test]
+fn missing_inputs_are_listed_without_prompt() {
+    let repository = TestRepository::new(Project::Webpack);
+    let output = repository.run(&["--project-type", "Webpack", "--flow-type", "Release"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("please give the inputs by flags or environment variables : --release-type (GITFLOW_RELEASE_TYPE) , --yes (GITFLOW_YES)"),
+        "{}",
+        stderr
+    );
+    assert_eq!(repository.rev_parse("origin/master"), repository.rev_parse("master"));
+}
+
+#[test]
+fn flow_inputs_are_read_from_environment() {
+    let repository = TestRepository::new(Project::Webpack);
+    let envs = [
+        ("GITFLOW_PROJECT_TYPE", "Webpack"),
+        ("GITFLOW_FLOW_TYPE", "Hotfix"),
+        ("GITFLOW_YES", "1"),
+    ];
+    let output = repository.run_with_envs(&[], &envs);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!("hotfix/1.2.1", repository.current_branch());
+    assert_eq!("1.2.1-SNAPSHOT", repository.working_version());
+}
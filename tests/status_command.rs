@@ -0,0 +1,33 @@
+mod common;
+
+use common::{release_tag_of, Project, TestRepository, SEED_TAG};
+
+#[test]
+fn status_shows_branches_and_next_versions() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.run_flow(&["--flow-type", "Hotfix"]);
+    repository.commit_file("fix.txt", "some fix");
+    let output = repository.run(&["--project-type", "Webpack", "status"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(SEED_TAG), "{}", stdout);
+    let hotfix_line = stdout.lines().find(|line| line.starts_with("│ hotfix/1.2.1")).unwrap();
+    assert!(hotfix_line.contains("local"), "{}", hotfix_line);
+    assert!(hotfix_line.contains("1.2.1-SNAPSHOT"), "{}", hotfix_line);
+    assert!(hotfix_line.contains("↑2 ↓0"), "{}", hotfix_line);
+    assert!(stdout.contains("feature/1.3.0"), "{}", stdout);
+    assert!(stdout.contains(&release_tag_of("1.3.0")), "{}", stdout);
+    assert!(stdout.contains(&release_tag_of("1.2.1")), "{}", stdout);
+}
+
+#[test]
+fn status_in_language_of_flag() {
+    let repository = TestRepository::new(Project::Webpack);
+    let output = repository.run(&["--project-type", "Webpack", "--lang", "zh", "status"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("当前分支"), "{}", stdout);
+    assert!(stdout.contains("最新Release Tag"), "{}", stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("[GIT]从远端拉取"), "{}", stderr);
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use common::{release_tag_of, Project, TestRepository};
+
+#[test]
+fn undo_restores_refs_of_last_flow() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file("feature.txt", "some feature");
+    let master = repository.rev_parse("master");
+    let develop = repository.rev_parse("develop");
+    let branches = repository.branches();
+    let tags = repository.tags();
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test"]);
+    assert!(repository.tags().contains(&release_tag_of("1.3.0")));
+
+    let output = repository.run(&["--yes", "undo"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(master, repository.rev_parse("master"));
+    assert_eq!(develop, repository.rev_parse("develop"));
+    assert_eq!(branches, repository.branches());
+    assert_eq!(tags, repository.tags());
+    assert_eq!("test/1.3.0", repository.current_branch());
+
+    // the undo is recorded, so the release is not undone again
+    let output = repository.run(&["--yes", "undo"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("There is no execution which changed branches or tags and is not undone yet"));
+    let output = repository.run(&["history"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Undo Release(Test)"));
+}
+
+#[test]
+fn undo_skips_executions_without_changes() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file("feature.txt", "some feature");
+    let master = repository.rev_parse("master");
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test"]);
+    // the test branch of 1.4.0 does not exist, nothing is changed
+    let output = repository.run(&["--project-type", "Webpack", "--yes", "--flow-type", "Release", "--release-type", "Test"]);
+    assert!(!output.status.success());
+
+    let output = repository.run(&["--yes", "undo"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(master, repository.rev_parse("master"));
+    assert!(!repository.tags().contains(&release_tag_of("1.3.0")));
+}
+
+#[test]
+fn undo_keeps_current_branch_after_detached_start() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "--detach", "develop"]);
+    repository.run_flow(&["--flow-type", "Feature"]);
+    assert_eq!("feature/1.3.0", repository.current_branch());
+
+    let output = repository.run(&["--yes", "undo"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("current branch feature/1.3.0 is deleted by the undo"), "{}", stderr);
+    assert!(repository.branches().contains(&String::from("feature/1.3.0")));
+
+    repository.git(&["switch", "develop"]);
+    let output = repository.run(&["--yes", "undo"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(!repository.branches().contains(&String::from("feature/1.3.0")));
+    assert_eq!("develop", repository.current_branch());
+}
+
+#[test]
+fn undo_is_refused_after_push() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file("feature.txt", "some feature");
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test"]);
+    let master = repository.rev_parse("master");
+    repository.git(&["push", "origin", "master", "--tags"]);
+
+    let output = repository.run(&["--yes", "undo"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("refs/heads/master has been pushed to remote"), "{}", stderr);
+    assert!(stderr.contains(&format!("refs/tags/{} has been pushed to remote", release_tag_of("1.3.0"))), "{}", stderr);
+    assert_eq!(master, repository.rev_parse("master"));
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use common::{Project, TestRepository, SEED_TAG};
+
+#[test]
+fn verify_versions_passes_on_consistent_repository() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.run_flow(&["--flow-type", "Hotfix"]);
+    let output = repository.run(&["--project-type", "Webpack", "verify-versions"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(stderr.contains("Versions are consistent"), "{}", stderr);
+}
+
+#[test]
+fn verify_versions_reports_inconsistencies_with_fix() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "feature/1.5.0", "develop"]);
+    repository.git(&["switch", "master"]);
+    repository.commit_version("back to : 1.4.0-SNAPSHOT", "1.4.0-SNAPSHOT");
+    let output = repository.run(&["--project-type", "Webpack", "verify-versions"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let feature_line = stdout.lines().find(|line| line.starts_with("│ feature/1.5.0")).unwrap();
+    assert!(feature_line.contains("1.3.0-SNAPSHOT"), "{}", feature_line);
+    assert!(
+        feature_line.contains("git switch feature/1.5.0 && npm pkg set version=1.5.0-SNAPSHOT"),
+        "{}",
+        feature_line
+    );
+    let develop_line = stdout.lines().find(|line| line.starts_with("│ develop")).unwrap();
+    assert!(develop_line.contains("git switch develop && git merge master"), "{}", develop_line);
+    assert!(!stdout.contains("│ master"), "{}", stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("2 version inconsistencies found"), "{}", stderr);
+}
+
+#[test]
+fn verify_versions_leaves_wrong_tag_to_be_fixed_manually() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["tag", "--force", SEED_TAG, "master"]);
+    let output = repository.run(&["--project-type", "Webpack", "verify-versions"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let tag_line = stdout.lines().find(|line| line.starts_with(&format!("│ {}", SEED_TAG))).unwrap();
+    assert!(tag_line.contains("1.3.0-SNAPSHOT"), "{}", tag_line);
+    assert!(tag_line.contains("Fix manually : find the release commit of 1.2.0.RELEASE"), "{}", tag_line);
+    assert!(!stdout.contains("git tag -d"), "{}", stdout);
+}
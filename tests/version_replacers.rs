@@ -0,0 +1,35 @@
+mod common;
+
+use common::{release_tag_of, Project, TestRepository};
+
+const CONFIG: &str = "[[version-replacers]]\nfile = \"src/version.ts\"\npattern = \"VERSION = '(?P<version>[^']+)'\"\n";
+
+#[test]
+fn release_updates_replaced_files() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "develop"]);
+    std::fs::create_dir_all(repository.work.join("src")).unwrap();
+    repository.commit_file("src/version.ts", "export const VERSION = '1.3.0-SNAPSHOT';\n");
+    repository.commit_file(".gitflow.toml", CONFIG);
+    repository.git(&["switch", "-c", "test/1.3.0"]);
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test"]);
+
+    let tag = release_tag_of("1.3.0");
+    assert!(repository.tags().contains(&tag));
+    assert_eq!("export const VERSION = '1.3.0.RELEASE';", repository.git(&["show", &format!("{}:src/version.ts", tag)]));
+    assert_eq!("export const VERSION = '1.4.0-SNAPSHOT';", repository.git(&["show", "develop:src/version.ts"]));
+}
+
+#[test]
+fn replacer_matching_nothing_stops_flow() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file(".gitflow.toml", CONFIG);
+    let master = repository.rev_parse("master");
+
+    let output = repository.run(&["--project-type", "Webpack", "--yes", "--flow-type", "Release", "--release-type", "Test"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Version replacer matches nothing , File : src/version.ts"), "{}", stderr);
+    assert_eq!(master, repository.rev_parse("master"));
+}
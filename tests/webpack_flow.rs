@@ -0,0 +1,309 @@
+mod common;
+
+use common::{release_tag_of, require_command, Project, TestRepository, SEED_TAG};
+
+fn feature_flow(git_backend: &str) {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.run_flow(&["--git-backend", git_backend, "--flow-type", "Feature"]);
+    assert_eq!("feature/1.3.0", repository.current_branch());
+    assert_eq!(repository.rev_parse("develop"), repository.rev_parse("feature/1.3.0"));
+    assert_eq!("1.3.0-SNAPSHOT", repository.working_version());
+}
+
+#[test]
+fn feature_flow_creates_branch_from_develop() {
+    feature_flow("Cli");
+}
+
+#[test]
+fn feature_flow_creates_branch_from_develop_with_libgit2() {
+    feature_flow("Libgit2");
+}
+
+#[test]
+fn feature_flow_switches_to_existing_remote_branch() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "feature/1.3.0", "develop"]);
+    repository.commit_file("feature.txt", "some feature");
+    repository.git(&["push", "origin", "feature/1.3.0"]);
+    repository.git(&["switch", "master"]);
+    repository.git(&["branch", "-D", "feature/1.3.0"]);
+    repository.run_flow(&["--flow-type", "Feature"]);
+    assert_eq!("feature/1.3.0", repository.current_branch());
+    assert_eq!("some feature", repository.commit_message("HEAD"));
+}
+
+fn hotfix_flow(git_backend: &str) {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.run_flow(&["--git-backend", git_backend, "--flow-type", "Hotfix"]);
+    assert_eq!("hotfix/1.2.1", repository.current_branch());
+    assert_eq!("1.2.1-SNAPSHOT", repository.version_at("hotfix/1.2.1"));
+    assert_eq!("new hotfix: 1.2.1", repository.commit_message("hotfix/1.2.1"));
+    assert_eq!(repository.rev_parse("master"), repository.rev_parse("hotfix/1.2.1^"));
+}
+
+#[test]
+fn hotfix_flow_creates_branch_from_master() {
+    hotfix_flow("Cli");
+}
+
+#[test]
+fn hotfix_flow_creates_branch_from_master_with_libgit2() {
+    hotfix_flow("Libgit2");
+}
+
+fn release_test_flow(git_backend: &str) {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file("feature.txt", "some feature");
+    repository.run_flow(&["--git-backend", git_backend, "--flow-type", "Release", "--release-type", "Test"]);
+    let tag_name = release_tag_of("1.3.0");
+    assert_eq!(vec![String::from(SEED_TAG), tag_name.clone()], repository.tags());
+    assert_eq!("1.3.0.RELEASE", repository.version_at(&tag_name));
+    assert_eq!("release: 1.3.0", repository.commit_message(&tag_name));
+    assert!(repository.is_ancestor("test/1.3.0", &tag_name));
+    assert_eq!("some feature", repository.git(&["show", &format!("{}:feature.txt", tag_name)]));
+    assert_eq!("1.4.0-SNAPSHOT", repository.version_at("master"));
+    assert_eq!("back to : 1.4.0-SNAPSHOT", repository.commit_message("master"));
+    assert!(repository.is_ancestor("master", "develop"));
+    assert_eq!("1.4.0-SNAPSHOT", repository.version_at("develop"));
+    assert_eq!("feature/1.4.0", repository.current_branch());
+    assert_eq!(repository.rev_parse("develop"), repository.rev_parse("feature/1.4.0"));
+    assert_eq!("", repository.git(&["status", "--porcelain", "--untracked-files=no"]));
+}
+
+#[test]
+fn release_test_flow_tags_master_and_starts_next_feature() {
+    release_test_flow("Cli");
+}
+
+#[test]
+fn release_test_flow_tags_master_and_starts_next_feature_with_libgit2() {
+    release_test_flow("Libgit2");
+}
+
+/// Release tags of another format, with a higher one which master does not contain
+fn custom_tag_format(git_backend: &str) {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.commit_file(".gitflow.toml", "[release]\ntag-format = \"release-{version}\"\n");
+    repository.git(&["tag", "release-1.2.0", SEED_TAG]);
+    repository.git(&["branch", "--force", "develop", "master"]);
+    repository.git(&["switch", "-c", "experiment", "master"]);
+    repository.commit_file("experiment.txt", "some experiment");
+    repository.git(&["tag", "release-9.0.0"]);
+    repository.git(&["switch", "master"]);
+    repository.git(&["push", "--force", "origin", "master", "develop"]);
+    repository.run_flow(&["--git-backend", git_backend, "--flow-type", "Hotfix"]);
+    assert_eq!("hotfix/1.2.1", repository.current_branch());
+    repository.run_flow(&["--git-backend", git_backend, "--flow-type", "Release", "--release-type", "Hotfix"]);
+    assert!(repository.tags().contains(&String::from("release-1.2.1")));
+    assert_eq!("1.2.1.RELEASE", repository.version_at("release-1.2.1"));
+}
+
+#[test]
+fn custom_tag_format_names_release_tags() {
+    custom_tag_format("Cli");
+}
+
+#[test]
+fn custom_tag_format_names_release_tags_with_libgit2() {
+    custom_tag_format("Libgit2");
+}
+
+#[test]
+fn release_hotfix_flow_tags_master_and_merges_into_develop() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.run_flow(&["--flow-type", "Hotfix"]);
+    repository.commit_file("fix.txt", "some fix");
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Hotfix"]);
+    let tag_name = release_tag_of("1.2.1");
+    assert!(repository.tags().contains(&tag_name));
+    assert_eq!("1.2.1.RELEASE", repository.version_at(&tag_name));
+    assert_eq!("release: 1.2.1", repository.commit_message(&tag_name));
+    assert!(repository.is_ancestor("hotfix/1.2.1", &tag_name));
+    assert_eq!("1.3.0-SNAPSHOT", repository.version_at("master"));
+    assert!(repository.is_ancestor("master", "develop"));
+    assert_eq!("1.3.0-SNAPSHOT", repository.version_at("develop"));
+    assert_eq!("develop", repository.current_branch());
+}
+
+#[test]
+fn release_specific_flow_tags_branch_version() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "feature/1.3.0", "develop"]);
+    repository.commit_file("feature.txt", "some feature");
+    repository.git(&["push", "--set-upstream", "origin", "feature/1.3.0"]);
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Specific", "--specific-branch", "feature/1.3.0"]);
+    let tag_name = release_tag_of("1.3.0");
+    assert_eq!("1.3.0.RELEASE", repository.version_at(&tag_name));
+    assert_eq!(repository.rev_parse("master"), repository.rev_parse(&tag_name));
+    assert!(repository.is_ancestor("feature/1.3.0", "master"));
+    assert_eq!("master", repository.current_branch());
+}
+
+/// package.json at 1.3.0-SNAPSHOT with the scripts
+fn package_json_with_scripts(scripts: &str) -> String {
+    format!(
+        "{{\n  \"name\": \"explore-webpack\",\n  \"version\": \"1.3.0-SNAPSHOT\",\n  \"private\": true,\n  \"scripts\": {{\n{}\n  }}\n}}\n",
+        scripts
+    )
+}
+
+#[test]
+fn failed_build_cancels_release() {
+    require_command("npm");
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file("package.json", &package_json_with_scripts("    \"build\": \"exit 2\""));
+    let master = repository.rev_parse("master");
+
+    let output = repository.run(&["--project-type", "Webpack", "--yes", "--flow-type", "Release", "--release-type", "Test"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Command <npm run build> failed with exit code 2"), "{}", stderr);
+    assert_eq!(vec![String::from(SEED_TAG)], repository.tags());
+    assert_eq!(master, repository.rev_parse("master"));
+    assert_eq!("", repository.git(&["status", "--porcelain"]));
+}
+
+#[test]
+fn test_verification_runs_test_script() {
+    require_command("npm");
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    let scripts = "    \"build\": \"echo build\",\n    \"test\": \"echo tested > ../tested.txt\"";
+    repository.commit_file("package.json", &package_json_with_scripts(scripts));
+    repository.run_flow(&["--verification", "Test", "--flow-type", "Release", "--release-type", "Test"]);
+    assert!(repository.work.join("../tested.txt").exists());
+    assert!(repository.tags().contains(&release_tag_of("1.3.0")));
+}
+
+#[test]
+fn build_output_stays_out_of_release() {
+    require_command("npm");
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    let scripts = "    \"build\": \"mkdir -p dist && echo bundle > dist/bundle.js\"";
+    repository.commit_file("package.json", &package_json_with_scripts(scripts));
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test"]);
+    let tag_name = release_tag_of("1.3.0");
+    assert_eq!("release: 1.3.0", repository.commit_message(&tag_name));
+    let released_files = repository.git(&["ls-tree", "-r", "--name-only", &tag_name]);
+    assert!(!released_files.contains("dist/bundle.js"), "{}", released_files);
+    assert!(!released_files.contains("package-lock.json"), "{}", released_files);
+}
+
+fn build_changing_tracked_file(git_backend: &str) {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file("banner.txt", "banner of the sources");
+    let scripts = "    \"build\": \"echo generated banner > banner.txt\"";
+    repository.commit_file("package.json", &package_json_with_scripts(scripts));
+    let master = repository.rev_parse("master");
+
+    let output = repository.run(&["--project-type", "Webpack", "--yes", "--git-backend", git_backend, "--flow-type", "Release", "--release-type", "Test"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("The verification changed tracked files, the changes can't be released : banner.txt"), "{}", stderr);
+    assert_eq!(vec![String::from(SEED_TAG)], repository.tags());
+    assert_eq!(master, repository.rev_parse("master"));
+    assert_eq!("", repository.git(&["status", "--porcelain", "--untracked-files=no"]));
+}
+
+#[test]
+fn build_changing_tracked_file_cancels_release() {
+    require_command("npm");
+    build_changing_tracked_file("Cli");
+}
+
+#[test]
+fn build_changing_tracked_file_cancels_release_with_libgit2() {
+    require_command("npm");
+    build_changing_tracked_file("Libgit2");
+}
+
+#[test]
+fn hotfix_from_earlier_tag_releases_on_its_line() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file("feature.txt", "some feature");
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test"]);
+
+    repository.run_flow(&["--flow-type", "Hotfix", "--from-tag", SEED_TAG]);
+    assert_eq!("hotfix/1.2.1", repository.current_branch());
+    assert_eq!("1.2.1-SNAPSHOT", repository.working_version());
+    assert_eq!(repository.rev_parse(SEED_TAG), repository.rev_parse("hotfix/1.2.1^1"));
+    repository.commit_file("fix.txt", "some fix");
+    let master = repository.rev_parse("master");
+    let develop = repository.rev_parse("develop");
+
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Hotfix", "--from-tag", SEED_TAG]);
+    let tag_name = release_tag_of("1.2.1");
+    assert_eq!("1.2.1.RELEASE", repository.version_at(&tag_name));
+    assert_eq!(repository.rev_parse("hotfix/1.2.1"), repository.rev_parse(&tag_name));
+    assert_eq!(master, repository.rev_parse("master"));
+    assert_eq!(develop, repository.rev_parse("develop"));
+}
+
+#[test]
+fn support_flow_hotfixes_on_support_branch() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file("feature.txt", "some feature");
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test"]);
+    let master = repository.rev_parse("master");
+    let develop = repository.rev_parse("develop");
+
+    repository.run_flow(&["--flow-type", "Support", "--from-tag", SEED_TAG]);
+    assert_eq!("support/1.2", repository.current_branch());
+    assert_eq!(repository.rev_parse(SEED_TAG), repository.rev_parse("support/1.2"));
+
+    repository.run_flow(&["--flow-type", "Hotfix", "--support-branch", "support/1.2"]);
+    assert_eq!("hotfix/1.2.1", repository.current_branch());
+    assert_eq!("1.2.1-SNAPSHOT", repository.working_version());
+    repository.commit_file("fix.txt", "some fix");
+
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Hotfix", "--support-branch", "support/1.2"]);
+    let tag_name = release_tag_of("1.2.1");
+    assert_eq!("1.2.1.RELEASE", repository.version_at(&tag_name));
+    assert_eq!(repository.rev_parse("support/1.2"), repository.rev_parse(&tag_name));
+    assert!(repository.is_ancestor("hotfix/1.2.1", "support/1.2"));
+    assert_eq!(master, repository.rev_parse("master"));
+    assert_eq!(develop, repository.rev_parse("develop"));
+}
+
+#[test]
+fn major_feature_is_released_by_test_flow() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.run_flow(&["--flow-type", "Feature", "--major"]);
+    assert_eq!("feature/2.0.0", repository.current_branch());
+    assert_eq!("2.0.0-SNAPSHOT", repository.working_version());
+    assert_eq!("1.3.0-SNAPSHOT", repository.version_at("develop"));
+    repository.git(&["switch", "-c", "test/2.0.0"]);
+    repository.commit_file("feature.txt", "breaking feature");
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test", "--major"]);
+    let tag_name = release_tag_of("2.0.0");
+    assert_eq!("2.0.0.RELEASE", repository.version_at(&tag_name));
+    assert_eq!("2.1.0-SNAPSHOT", repository.version_at("develop"));
+    assert_eq!("feature/2.1.0", repository.current_branch());
+
+    repository.run_flow(&["--flow-type", "Hotfix"]);
+    assert_eq!("hotfix/2.0.1", repository.current_branch());
+}
+
+#[test]
+fn candidate_release_tags_test_branch() {
+    let repository = TestRepository::new(Project::Webpack);
+    repository.git(&["switch", "-c", "test/1.3.0", "develop"]);
+    repository.commit_file("feature.txt", "some feature");
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Candidate"]);
+    assert_eq!("1.3.0-RC.1", repository.version_at("v1.3.0-RC.1"));
+    assert_eq!("release candidate: 1.3.0-RC.1", repository.commit_message("v1.3.0-RC.1"));
+    assert_eq!("1.3.0-SNAPSHOT", repository.version_at("test/1.3.0"));
+    assert_eq!("test/1.3.0", repository.current_branch());
+
+    repository.run_flow(&["--flow-type", "Release", "--release-type", "Test"]);
+    let tag_name = release_tag_of("1.3.0");
+    assert_eq!("1.3.0.RELEASE", repository.version_at(&tag_name));
+    assert!(repository.git(&["log", "-1", "--format=%b", &tag_name]).contains("promoted from v1.3.0-RC.1"));
+}